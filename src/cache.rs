@@ -0,0 +1,86 @@
+use bytes::Bytes;
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// In-memory cache for response bodies, keyed by the request's route.
+pub(crate) struct Cache {
+    ttl: Duration,
+    entries: Mutex<HashMap<Box<str>, (Instant, Bytes)>>,
+}
+
+impl Cache {
+    /// Maximum amount of cached responses.
+    pub(crate) const CAPACITY: usize = 1000;
+
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The cached response for the given route if it has not expired yet.
+    pub(crate) fn get(&self, key: &str) -> Option<Bytes> {
+        let entries = self.entries.lock().ok()?;
+
+        entries
+            .get(key)
+            .filter(|(inserted, _)| inserted.elapsed() < self.ttl)
+            .map(|(_, bytes)| bytes.clone())
+    }
+
+    /// Cache a response, making room by removing expired
+    /// and, if necessary, the oldest entries.
+    pub(crate) fn insert(&self, key: Box<str>, bytes: Bytes) {
+        let mut entries = match self.entries.lock() {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+
+        if entries.len() >= Self::CAPACITY && !entries.contains_key(&key) {
+            let ttl = self.ttl;
+            entries.retain(|_, (inserted, _)| inserted.elapsed() < ttl);
+
+            if entries.len() >= Self::CAPACITY {
+                let oldest = entries
+                    .iter()
+                    .min_by_key(|(_, (inserted, _))| *inserted)
+                    .map(|(key, _)| key.clone());
+
+                if let Some(oldest) = oldest {
+                    entries.remove(&oldest);
+                }
+            }
+        }
+
+        entries.insert(key, (Instant::now(), bytes));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_capacity() {
+        let cache = Cache::new(Duration::from_secs(60));
+
+        for i in 0..=Cache::CAPACITY {
+            cache.insert(i.to_string().into_boxed_str(), Bytes::new());
+        }
+
+        assert_eq!(cache.entries.lock().unwrap().len(), Cache::CAPACITY);
+        assert!(cache.get("0").is_none());
+        assert!(cache.get(&Cache::CAPACITY.to_string()).is_some());
+    }
+
+    #[test]
+    fn cache_ttl() {
+        let cache = Cache::new(Duration::from_secs(0));
+        cache.insert("key".into(), Bytes::new());
+        assert!(cache.get("key").is_none());
+    }
+}
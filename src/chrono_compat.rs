@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use time::OffsetDateTime;
+
+/// Convert a [`OffsetDateTime`] into a [`DateTime<Utc>`](DateTime).
+///
+/// # Example
+/// ```
+/// use rosu::chrono_compat::to_chrono;
+/// use time::OffsetDateTime;
+///
+/// let date = OffsetDateTime::from_unix_timestamp(1_546_498_888).unwrap();
+/// assert_eq!(to_chrono(date).timestamp(), 1_546_498_888);
+/// ```
+pub fn to_chrono(date: OffsetDateTime) -> DateTime<Utc> {
+    DateTime::from_timestamp(date.unix_timestamp(), date.nanosecond())
+        .expect("OffsetDateTime is always within the range of DateTime<Utc>")
+}
+
+/// Convert a [`DateTime<Utc>`](DateTime) into a [`OffsetDateTime`].
+///
+/// Returns `None` if the date is out of range for [`OffsetDateTime`].
+pub fn from_chrono(date: DateTime<Utc>) -> Option<OffsetDateTime> {
+    OffsetDateTime::from_unix_timestamp(date.timestamp())
+        .ok()?
+        .replace_nanosecond(date.timestamp_subsec_nanos())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chrono_conversion() {
+        let date = OffsetDateTime::from_unix_timestamp_nanos(1_546_498_888_123_456_789).unwrap();
+        let chrono_date = to_chrono(date);
+        assert_eq!(chrono_date.to_rfc3339(), "2019-01-03T07:01:28.123456789+00:00");
+        assert_eq!(from_chrono(chrono_date), Some(date));
+    }
+}
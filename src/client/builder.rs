@@ -1,18 +1,43 @@
-use super::{Osu, OsuRef};
-use crate::{ratelimit::RateLimiter, OsuError, OsuResult};
+use super::{Osu, OsuRef, BASE_URL, USER_AGENT};
+use crate::{
+    ratelimit::RateLimiter,
+    request::{GetScores, GetUserBest, GetUserRecent},
+    OsuError, OsuResult,
+};
 
+#[cfg(feature = "record")]
+use super::record::Fixtures;
+#[cfg(feature = "mem-cache")]
+use crate::cache::Cache;
 #[cfg(feature = "metrics")]
 use crate::metrics::Metrics;
 
-use reqwest::ClientBuilder as ReqwestClientBuilder;
+use reqwest::{
+    header::{HeaderMap, USER_AGENT as USER_AGENT_HEADER},
+    ClientBuilder as ReqwestClientBuilder,
+};
 use std::{sync::Arc, time::Duration};
 
+#[cfg(feature = "record")]
+use std::path::PathBuf;
+
 /// A builder for the main [`Osu`] client.
 #[derive(Debug)]
 pub struct OsuBuilder {
     reqwest_client: Option<ReqwestClientBuilder>,
     timeout: Duration,
     api_key: Box<str>,
+    base_url: Box<str>,
+    client_name: Option<String>,
+    default_headers: HeaderMap,
+    ratelimit: bool,
+    default_top_limit: Option<u32>,
+    default_recent_limit: Option<u32>,
+    default_leaderboard_limit: Option<u32>,
+    #[cfg(feature = "mem-cache")]
+    cache_ttl: Option<Duration>,
+    #[cfg(feature = "record")]
+    fixture_dir: Option<PathBuf>,
 }
 
 impl OsuBuilder {
@@ -22,6 +47,17 @@ impl OsuBuilder {
             timeout: Duration::from_secs(10),
             reqwest_client: None,
             api_key: api_key.into(),
+            base_url: BASE_URL.into(),
+            client_name: None,
+            default_headers: HeaderMap::new(),
+            ratelimit: true,
+            default_top_limit: None,
+            default_recent_limit: None,
+            default_leaderboard_limit: None,
+            #[cfg(feature = "mem-cache")]
+            cache_ttl: None,
+            #[cfg(feature = "record")]
+            fixture_dir: None,
         }
     }
 
@@ -33,22 +69,141 @@ impl OsuBuilder {
     pub fn build(self) -> OsuResult<Osu> {
         let http = self
             .reqwest_client
-            .unwrap_or_else(ReqwestClientBuilder::new)
+            .unwrap_or_default()
             .timeout(self.timeout)
             .build()
             .map_err(OsuError::BuildingClient)?;
 
+        let user_agent = match self.client_name {
+            Some(ref name) => format!("{} {}", USER_AGENT, name).into_boxed_str(),
+            None => USER_AGENT.into(),
+        };
+
+        let ratelimiter = if self.ratelimit {
+            RateLimiter::new(15, 1)
+        } else {
+            RateLimiter::disabled()
+        };
+
         let inner = OsuRef {
             http,
+            user_agent,
+            default_headers: self.default_headers,
             api_key: self.api_key,
-            ratelimiter: RateLimiter::new(15, 1),
+            base_url: self.base_url,
+            ratelimiter,
+            default_top_limit: self.default_top_limit,
+            default_recent_limit: self.default_recent_limit,
+            default_leaderboard_limit: self.default_leaderboard_limit,
             #[cfg(feature = "metrics")]
-            metrics: Metrics::new(),
+            metrics: Metrics::new(
+                self.client_name
+                    .as_deref()
+                    .unwrap_or(Metrics::DEFAULT_CLIENT),
+            ),
+            #[cfg(feature = "dedup")]
+            in_flight: Default::default(),
+            #[cfg(feature = "mem-cache")]
+            cache: self.cache_ttl.map(Cache::new),
+            #[cfg(feature = "record")]
+            fixtures: self.fixture_dir.map(Fixtures::new),
         };
 
         Ok(Osu(Arc::new(inner)))
     }
 
+    /// Set the url that all request routes are appended to,
+    /// defaults to `https://osu.ppy.sh/api/`.
+    ///
+    /// This is mostly useful to test against a mock server.
+    pub fn base_url(mut self, base_url: impl Into<Box<str>>) -> Self {
+        self.base_url = base_url.into();
+
+        self
+    }
+
+    /// Cache user and beatmap responses for the given duration.
+    ///
+    /// Identical requests within that duration resolve to the cached response
+    /// instead of requesting the api again. The cache holds up to 1000 responses.
+    /// Responses are not cached unless a duration is specified.
+    #[cfg(feature = "mem-cache")]
+    pub fn cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl.replace(ttl);
+
+        self
+    }
+
+    /// Record responses as JSON fixtures in the given directory and replay them afterwards.
+    ///
+    /// Each route is requested from the api only if its fixture does not exist yet,
+    /// its successful response is then stored as fixture.
+    /// This way tests can run against recorded responses instead of the live api.
+    #[cfg(feature = "record")]
+    pub fn fixture_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.fixture_dir.replace(dir.into());
+
+        self
+    }
+
+    /// Name the client, e.g. to distinguish multiple clients of the same application.
+    ///
+    /// The name is appended to the user agent and, with the `metrics` feature,
+    /// used as value of the metrics' `client` label.
+    pub fn client_name(mut self, name: impl Into<String>) -> Self {
+        self.client_name.replace(name.into());
+
+        self
+    }
+
+    /// Set headers that are sent with every request, e.g. to authenticate at a gateway.
+    ///
+    /// A `User-Agent` header is ignored, see [`OsuBuilder::client_name`] to adjust it instead.
+    pub fn default_headers(mut self, mut headers: HeaderMap) -> Self {
+        headers.remove(USER_AGENT_HEADER);
+        self.default_headers = headers;
+
+        self
+    }
+
+    /// Set the limit for top score requests that don't specify one,
+    /// capped at [`GetUserBest::MAX_LIMIT`].
+    pub fn default_top_limit(mut self, limit: u32) -> Self {
+        self.default_top_limit
+            .replace(limit.min(GetUserBest::MAX_LIMIT));
+
+        self
+    }
+
+    /// Set the limit for recent score requests that don't specify one,
+    /// capped at [`GetUserRecent::MAX_LIMIT`].
+    pub fn default_recent_limit(mut self, limit: u32) -> Self {
+        self.default_recent_limit
+            .replace(limit.min(GetUserRecent::MAX_LIMIT));
+
+        self
+    }
+
+    /// Set the limit for beatmap leaderboard requests that don't specify one,
+    /// between 1 and [`GetScores::MAX_LIMIT`].
+    pub fn default_leaderboard_limit(mut self, limit: u32) -> Self {
+        self.default_leaderboard_limit
+            .replace(limit.clamp(1, GetScores::MAX_LIMIT));
+
+        self
+    }
+
+    /// Disable the ratelimiter so that requests are sent without any delay.
+    ///
+    /// **Only use this to test against a mock server.**
+    /// The osu!api will reject clients that exceed its ratelimit
+    /// and may ban the api key if it keeps happening.
+    pub fn disable_ratelimit(mut self) -> Self {
+        self.ratelimit = false;
+
+        self
+    }
+
     /// Set a pre-configured reqwest client builder to build off of.
     ///
     /// The timeout settings in the reqwest client will be overwritten by
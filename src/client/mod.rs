@@ -1,28 +1,50 @@
 mod builder;
+#[cfg(feature = "record")]
+mod record;
 
 pub use builder::OsuBuilder;
 
 use crate::{
     error::ApiError,
-    ratelimit::RateLimiter,
+    model::{Beatmap, GameMode, GameMods, Match, MatchUserStats, Score, User},
+    ratelimit::{RateLimiter, RatelimitInfo},
     request::{
-        GetBeatmap, GetBeatmaps, GetMatch, GetScore, GetScores, GetUser, GetUserBest,
+        GetBeatmap, GetBeatmaps, GetMatch, GetReplay, GetScore, GetScores, GetUser, GetUserBest,
         GetUserRecent, Request, UserIdentification,
     },
     routing::Route,
     OsuError, OsuResult,
 };
 
+#[cfg(feature = "mem-cache")]
+use crate::cache::Cache;
 #[cfg(feature = "metrics")]
 use crate::metrics::Metrics;
 
+#[cfg(feature = "record")]
+use record::Fixtures;
+
 use bytes::Bytes;
-use reqwest::{Client, Method, Response, StatusCode};
-use std::sync::Arc;
+use futures_util::stream::{self, Stream};
+use reqwest::{
+    header::{HeaderMap, USER_AGENT as USER_AGENT_HEADER},
+    Client, Method, Response, StatusCode,
+};
+use std::{sync::Arc, time::Duration};
+use tokio::time::sleep;
 
 #[cfg(feature = "metrics")]
 use prometheus::IntCounterVec;
 
+#[cfg(feature = "dedup")]
+use futures_util::future::{FutureExt, Shared};
+#[cfg(feature = "dedup")]
+use std::{collections::HashMap, future::Future, pin::Pin, sync::Mutex};
+
+#[cfg(feature = "dedup")]
+type SharedRequest =
+    Shared<Pin<Box<dyn Future<Output = Result<Bytes, Arc<OsuError>>> + Send + 'static>>>;
+
 const USER_AGENT: &str = concat!(
     "(",
     env!("CARGO_PKG_HOMEPAGE"),
@@ -31,16 +53,36 @@ const USER_AGENT: &str = concat!(
     ") rosu"
 );
 
+const BASE_URL: &str = "https://osu.ppy.sh/api/";
+
 pub(crate) struct OsuRef {
     http: Client,
+    user_agent: Box<str>,
+    default_headers: HeaderMap,
     ratelimiter: RateLimiter,
     api_key: Box<str>,
+    base_url: Box<str>,
+    pub(crate) default_top_limit: Option<u32>,
+    pub(crate) default_recent_limit: Option<u32>,
+    pub(crate) default_leaderboard_limit: Option<u32>,
     #[cfg(feature = "metrics")]
     pub(crate) metrics: Metrics,
+    #[cfg(feature = "dedup")]
+    in_flight: Mutex<HashMap<Box<str>, SharedRequest>>,
+    #[cfg(feature = "mem-cache")]
+    cache: Option<Cache>,
+    #[cfg(feature = "record")]
+    fixtures: Option<Fixtures>,
 }
 
 /// The main osu client.
 /// Cheap to clone.
+///
+/// Requests are lazy: they neither count towards the metrics nor wait for
+/// the ratelimiter until their future is polled for the first time.
+/// Dropping a future while it waits for the ratelimiter does not consume
+/// an access, dropping it after the request was sent does.
+#[derive(Clone)]
 pub struct Osu(pub(crate) Arc<OsuRef>);
 
 impl Osu {
@@ -50,10 +92,22 @@ impl Osu {
 
         let osu = OsuRef {
             http: Client::new(),
+            user_agent: USER_AGENT.into(),
+            default_headers: HeaderMap::new(),
             api_key: api_key.into(),
+            base_url: BASE_URL.into(),
             ratelimiter,
+            default_top_limit: None,
+            default_recent_limit: None,
+            default_leaderboard_limit: None,
             #[cfg(feature = "metrics")]
-            metrics: Metrics::new(),
+            metrics: Metrics::new(Metrics::DEFAULT_CLIENT),
+            #[cfg(feature = "dedup")]
+            in_flight: Mutex::new(HashMap::new()),
+            #[cfg(feature = "mem-cache")]
+            cache: None,
+            #[cfg(feature = "record")]
+            fixtures: None,
         };
 
         Self(Arc::new(osu))
@@ -69,7 +123,125 @@ impl Osu {
         GetUser::new(self, user)
     }
 
+    /// Request a [`User`](crate::model::User) once for each of the given modes.
+    ///
+    /// The resulting vec preserves the order of `modes`,
+    /// skipping the modes for which no user was found.
+    pub async fn user_multi_mode(
+        &self,
+        user: impl Into<UserIdentification>,
+        modes: &[GameMode],
+    ) -> OsuResult<Vec<(GameMode, User)>> {
+        let user = user.into();
+        let mut users = Vec::with_capacity(modes.len());
+
+        for &mode in modes {
+            if let Some(user) = self.user(user.clone()).mode(mode).await? {
+                users.push((mode, user));
+            }
+        }
+
+        Ok(users)
+    }
+
+    /// Request a [`User`](crate::model::User) in the given mode,
+    /// resolving to `None` if the user was not found or is not ranked.
+    ///
+    /// See [`User::is_ranked`](crate::model::User::is_ranked).
+    pub async fn user_if_ranked(
+        &self,
+        user: impl Into<UserIdentification>,
+        mode: GameMode,
+    ) -> OsuResult<Option<User>> {
+        let user = self.user(user).mode(mode).await?;
+
+        Ok(user.filter(User::is_ranked))
+    }
+
+    /// Request the given users in the given mode and sort them by pp, highest first.
+    ///
+    /// Users are requested one after another and those that are not found are skipped.
+    /// Users with equal pp keep their order of `users`.
+    pub async fn users_sorted_by_pp(
+        &self,
+        users: Vec<UserIdentification>,
+        mode: GameMode,
+    ) -> OsuResult<Vec<User>> {
+        let mut sorted = Vec::with_capacity(users.len());
+
+        for user in users {
+            if let Some(user) = self.user(user).mode(mode).await? {
+                sorted.push(user);
+            }
+        }
+
+        sorted.sort_by(|a, b| b.pp_raw.total_cmp(&a.pp_raw));
+
+        Ok(sorted)
+    }
+
+    /// Request the given user and the users of `cohort` in the given mode and
+    /// return the user's 1-based position when sorted by pp, highest first.
+    ///
+    /// Useful for leaderboards among friends or clan members.
+    /// Cohort users that are not found are skipped and on equal pp the given user ranks first.
+    /// Returns `None` if the user itself was not found.
+    pub async fn rank_within(
+        &self,
+        user_id: u32,
+        cohort: &[u32],
+        mode: GameMode,
+    ) -> OsuResult<Option<usize>> {
+        let users = std::iter::once(user_id)
+            .chain(cohort.iter().copied().filter(|&id| id != user_id))
+            .map(UserIdentification::Id)
+            .collect();
+
+        let sorted = self.users_sorted_by_pp(users, mode).await?;
+        let position = sorted.iter().position(|user| user.user_id == user_id);
+
+        Ok(position.map(|idx| idx + 1))
+    }
+
+    /// Request up to `limit` recent scores of the given user in the given mode and count
+    /// how often each beatmap was played, e.g. to see which maps the user is currently grinding.
+    ///
+    /// The resulting vec pairs beatmap ids with their play count, most played first.
+    /// Maps with equal play count are ordered by their most recent play.
+    pub async fn recent_map_frequency(
+        &self,
+        user: impl Into<UserIdentification>,
+        mode: GameMode,
+        limit: u32,
+    ) -> OsuResult<Vec<(u32, usize)>> {
+        let scores = self.recent_scores(user).mode(mode).limit(limit).await?;
+        let mut counts: Vec<(u32, usize)> = Vec::new();
+
+        for map_id in scores.iter().filter_map(|score| score.beatmap_id) {
+            match counts.iter_mut().find(|(id, _)| *id == map_id) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((map_id, 1)),
+            }
+        }
+
+        counts.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        Ok(counts)
+    }
+
+    /// Resolve a username to the user's id and the username in its canonical casing.
+    ///
+    /// Returns `None` if no user with the given name was found.
+    pub async fn resolve_user(&self, name: &str) -> OsuResult<Option<(u32, String)>> {
+        let user = self.user(name).await?;
+
+        Ok(user.map(|user| (user.user_id, user.username)))
+    }
+
     /// Request an optional [`Beatmap`](crate::model::Beatmap).
+    ///
+    /// If no beatmap matches the specified parameters, e.g. an unknown map id,
+    /// the request resolves to `Ok(None)` instead of an error.
     pub fn beatmap(&self) -> GetBeatmap<'_> {
         GetBeatmap::new(self)
     }
@@ -79,11 +251,163 @@ impl Osu {
         GetBeatmaps::new(self)
     }
 
+    /// Collect up to `total` [`Beatmap`](crate::model::Beatmap)s with the parameters of `base`,
+    /// exceeding the api's limit of 500 beatmaps per request.
+    ///
+    /// Pages of 500 beatmaps are requested one after another, each one starting at the approved
+    /// date of the previous page's last beatmap, until `total` unique beatmaps are collected or no
+    /// more beatmaps are available. The limit of `base` is ignored and filters such as
+    /// [`stars_between`](GetBeatmaps::stars_between) are applied to the collected beatmaps
+    /// so the result may contain fewer than `total` beatmaps.
+    pub async fn beatmaps_collect(
+        &self,
+        base: GetBeatmaps<'_>,
+        total: usize,
+    ) -> OsuResult<Vec<Beatmap>> {
+        base.collect(self, total).await
+    }
+
+    /// Request the [`Beatmap`](crate::model::Beatmap) with the given `map_id`
+    /// once for each of the given mod sets, e.g. to compare their star ratings.
+    ///
+    /// The resulting vec preserves the order of `mod_sets`,
+    /// skipping the mod sets for which no beatmap was found.
+    pub async fn beatmap_with_mod_variants(
+        &self,
+        map_id: u32,
+        mod_sets: &[GameMods],
+    ) -> OsuResult<Vec<(GameMods, Beatmap)>> {
+        let mut maps = Vec::with_capacity(mod_sets.len());
+
+        for &mods in mod_sets {
+            if let Some(map) = self.beatmap().map_id(map_id).mods(mods).await? {
+                maps.push((mods, map));
+            }
+        }
+
+        Ok(maps)
+    }
+
+    /// Request the best [`Score`](crate::model::Score) of the given user
+    /// on each of the given maps, e.g. on all maps of a tournament mappool.
+    ///
+    /// The maps are requested one after another and the resulting vec preserves
+    /// the order of `map_ids`, pairing each map id with the user's score, if any.
+    pub async fn scores_for_maps(
+        &self,
+        map_ids: &[u32],
+        user: impl Into<UserIdentification> + Clone,
+        mode: GameMode,
+    ) -> OsuResult<Vec<(u32, Option<Score>)>> {
+        let mut scores = Vec::with_capacity(map_ids.len());
+
+        for &map_id in map_ids {
+            let score = self.score(map_id).user(user.clone()).mode(mode).await?;
+            scores.push((map_id, score));
+        }
+
+        Ok(scores)
+    }
+
+    /// Request the [`Beatmap`](crate::model::Beatmap) of the given mapset
+    /// whose difficulty name matches `version`, ignoring case.
+    pub async fn beatmap_in_set(
+        &self,
+        mapset_id: u32,
+        version: &str,
+    ) -> OsuResult<Option<Beatmap>> {
+        let maps = self.beatmaps().mapset_id(mapset_id).await?;
+        let version = version.to_lowercase();
+
+        let map = maps
+            .into_iter()
+            .find(|map| map.version.to_lowercase() == version);
+
+        Ok(map)
+    }
+
     /// Request the [`Match`](crate::model::Match) with the given `match_id`.
     pub fn osu_match(&self, match_id: u32) -> GetMatch<'_> {
         GetMatch::new(self, match_id)
     }
 
+    /// Request the [`Match`](crate::model::Match) with the given `match_id` and aggregate
+    /// its scores per user id, sorted by total score, highest first.
+    ///
+    /// See [`Match::user_stats`] for how the stats are aggregated.
+    pub async fn match_scoreboard(&self, match_id: u32) -> OsuResult<Vec<(u32, MatchUserStats)>> {
+        let osu_match = self.osu_match(match_id).await?;
+        let mut scoreboard: Vec<_> = osu_match.user_stats().into_iter().collect();
+        scoreboard.sort_by(|(a_id, a), (b_id, b)| {
+            b.total_score
+                .cmp(&a.total_score)
+                .then_with(|| a_id.cmp(b_id))
+        });
+
+        Ok(scoreboard)
+    }
+
+    /// Poll the [`Match`](crate::model::Match) with the given `match_id` every `interval`.
+    ///
+    /// The match is yielded on the first poll and afterwards only when it
+    /// contains new games or when it ended.
+    /// The stream completes after yielding the ended match or after yielding an error.
+    pub fn watch_match(
+        &self,
+        match_id: u32,
+        interval: Duration,
+    ) -> impl Stream<Item = OsuResult<Match>> + '_ {
+        #[derive(Copy, Clone)]
+        enum State {
+            Init,
+            Watching { games: usize },
+            Done,
+        }
+
+        stream::unfold(State::Init, move |mut state| async move {
+            loop {
+                let games = match state {
+                    State::Init => None,
+                    State::Watching { games } => Some(games),
+                    State::Done => return None,
+                };
+
+                if games.is_some() {
+                    sleep(interval).await;
+                }
+
+                let osu_match = match self.osu_match(match_id).await {
+                    Ok(osu_match) => osu_match,
+                    Err(err) => return Some((Err(err), State::Done)),
+                };
+
+                if osu_match.end_time.is_some() {
+                    return Some((Ok(osu_match), State::Done));
+                }
+
+                let len = osu_match.games.len();
+                state = State::Watching { games: len };
+
+                let has_new_games = match games {
+                    Some(games) => len > games,
+                    None => true,
+                };
+
+                if has_new_games {
+                    return Some((Ok(osu_match), state));
+                }
+            }
+        })
+    }
+
+    /// Request the [`Replay`](crate::model::Replay) of the given user's score on the given `map_id`.
+    ///
+    /// Resolves to [`OsuError::ReplayUnavailable`] if the score has no replay available.
+    /// Note that the api only allows 10 replay requests per minute.
+    pub fn replay(&self, map_id: u32, user: impl Into<UserIdentification>) -> GetReplay<'_> {
+        GetReplay::new(self, map_id, user)
+    }
+
     /// Request an optional [`Score`](crate::model::Score) on the given `map_id`.
     pub fn score(&self, map_id: u32) -> GetScore<'_> {
         GetScore::new(self, map_id)
@@ -104,27 +428,169 @@ impl Osu {
         GetUserRecent::new(self, user)
     }
 
+    /// Request the most recent [`Score`](crate::model::Score) of the given user.
+    ///
+    /// Use [`GetUserRecent::latest`] to resolve directly to an optional score.
+    pub fn most_recent_score(&self, user: impl Into<UserIdentification>) -> GetUserRecent<'_> {
+        GetUserRecent::new(self, user).limit(1)
+    }
+
     #[cfg(feature = "metrics")]
     /// Returns an [`IntCounterVec`] from [`prometheus`] containing a counter for each request type.
     ///
+    /// The counters are labeled with `type` and `client`, the latter being the name specified
+    /// through [`OsuBuilder::client_name`] or `rosu` by default.
+    ///
     /// [`IntCounterVec`]: crate::prelude::IntCounterVec
     /// [`prometheus`]: https://crates.io/crates/prometheus
     pub fn metrics(&self) -> IntCounterVec {
         self.0.metrics.counters.clone()
     }
 
+    /// Returns the ratelimit information of the most recent response that contained
+    /// `X-RateLimit-Remaining` or `X-RateLimit-Reset` headers, if any.
+    pub fn last_ratelimit_info(&self) -> Option<RatelimitInfo> {
+        self.0.ratelimiter.last_info()
+    }
+
+    #[cfg(feature = "metrics")]
+    /// Returns how many requests were delayed by the ratelimiter and how many were sent immediately.
+    pub fn ratelimit_stats(&self) -> (u64, u64) {
+        self.0.ratelimiter.stats()
+    }
+
+    #[cfg(not(feature = "mem-cache"))]
     pub(crate) async fn request_bytes(&self, route: Route) -> OsuResult<Bytes> {
+        self.send_request(Request::from(route)).await
+    }
+
+    /// User and beatmap responses are cached if a cache ttl was specified.
+    #[cfg(feature = "mem-cache")]
+    pub(crate) async fn request_bytes(&self, route: Route) -> OsuResult<Bytes> {
+        let cache = self.0.cache.as_ref().filter(|_| route.is_cacheable());
         let req = Request::from(route);
+
+        let cache = match cache {
+            Some(cache) => cache,
+            None => return self.send_request(req).await,
+        };
+
+        if let Some(bytes) = cache.get(req.uri()) {
+            return Ok(bytes);
+        }
+
+        let key = req.0.clone();
+        let bytes = self.send_request(req).await?;
+        cache.insert(key, bytes.clone());
+
+        Ok(bytes)
+    }
+
+    #[cfg(not(feature = "dedup"))]
+    async fn send_request(&self, req: Request) -> OsuResult<Bytes> {
+        self.fetch_bytes(req).await
+    }
+
+    /// Concurrent requests with the same route share a single response.
+    #[cfg(feature = "dedup")]
+    async fn send_request(&self, req: Request) -> OsuResult<Bytes> {
+        let key = req.0.clone();
+
+        let fut = self
+            .0
+            .in_flight
+            .lock()
+            .unwrap()
+            .entry(key.clone())
+            .or_insert_with(|| {
+                let osu = self.clone();
+
+                let fut: Pin<Box<dyn Future<Output = _> + Send>> =
+                    Box::pin(async move { osu.fetch_bytes(req).await.map_err(Arc::new) });
+
+                fut.shared()
+            })
+            .clone();
+
+        let res = fut.clone().await;
+
+        {
+            let mut in_flight = self.0.in_flight.lock().unwrap();
+
+            if matches!(in_flight.get(&key), Some(pending) if pending.ptr_eq(&fut)) {
+                in_flight.remove(&key);
+            }
+        }
+
+        drop(fut);
+
+        res.map_err(|err| OsuError::from_shared(&err))
+    }
+
+    #[cfg(not(feature = "record"))]
+    async fn fetch_bytes(&self, req: Request) -> OsuResult<Bytes> {
+        self.download_bytes(req).await
+    }
+
+    /// Responses are replayed from fixtures if a fixture directory was specified.
+    /// Routes without fixture are requested and their successful responses recorded.
+    #[cfg(feature = "record")]
+    async fn fetch_bytes(&self, req: Request) -> OsuResult<Bytes> {
+        let fixtures = match self.0.fixtures {
+            Some(ref fixtures) => fixtures,
+            None => return self.download_bytes(req).await,
+        };
+
+        if let Some(bytes) = fixtures.replay(req.uri()) {
+            return Ok(bytes);
+        }
+
+        let key = req.0.clone();
+        let bytes = self.download_bytes(req).await?;
+        fixtures.record(&key, &bytes);
+
+        Ok(bytes)
+    }
+
+    async fn download_bytes(&self, req: Request) -> OsuResult<Bytes> {
+        #[cfg(not(feature = "tracing"))]
         let resp = self.make_request(req).await?;
+        #[cfg(feature = "tracing")]
+        let resp = self.make_traced_request(req).await?;
+
         resp.bytes().await.map_err(OsuError::ChunkingResponse)
     }
 
+    /// Make the request within a span that records either the response status or the error.
+    #[cfg(feature = "tracing")]
+    async fn make_traced_request(&self, req: Request) -> OsuResult<Response> {
+        use tracing::{field, Instrument};
+
+        let span = tracing::info_span!(
+            "request",
+            endpoint = req.endpoint(),
+            status = field::Empty,
+            error = field::Empty,
+        );
+
+        let res = self.make_request(req).instrument(span.clone()).await;
+
+        match res {
+            Ok(ref resp) => span.record("status", resp.status().as_u16()),
+            Err(ref err) => span.record("error", field::display(err)),
+        };
+
+        res
+    }
+
     async fn make_request(&self, req: Request) -> OsuResult<Response> {
         let resp = self.raw(req).await?;
         let status = resp.status();
+        self.0.ratelimiter.observe(resp.headers());
 
         match status {
             StatusCode::OK => return Ok(resp),
+            StatusCode::NOT_FOUND => return Err(OsuError::NotFound),
             StatusCode::SERVICE_UNAVAILABLE => {
                 let body = resp.text().await.ok();
                 return Err(OsuError::ServiceUnavailable(body));
@@ -141,6 +607,10 @@ impl Osu {
             Err(source) => return Err(OsuError::Parsing { body, source }),
         };
 
+        if error.is_unauthorized(status) {
+            return Err(OsuError::Unauthorized(error.error));
+        }
+
         Err(OsuError::Response {
             body,
             error,
@@ -148,10 +618,13 @@ impl Osu {
         })
     }
 
-    async fn raw(&self, Request(query): Request) -> OsuResult<Response> {
-        let mut url = String::with_capacity(26 + query.len() + self.0.api_key.len());
-        url.push_str("https://osu.ppy.sh/api/");
-        url.push_str(query.as_ref());
+    async fn raw(&self, req: Request) -> OsuResult<Response> {
+        let query = req.uri();
+        let base_url = &self.0.base_url;
+        let mut url =
+            String::with_capacity(base_url.len() + query.len() + self.0.api_key.len() + 3);
+        url.push_str(base_url);
+        url.push_str(query);
 
         self.0.ratelimiter.await_access().await;
 
@@ -162,7 +635,9 @@ impl Osu {
 
         let mut builder = self.0.http.request(Method::GET, &url);
 
-        builder = builder.header("User-Agent", USER_AGENT);
+        builder = builder
+            .headers(self.0.default_headers.clone())
+            .header(USER_AGENT_HEADER, self.0.user_agent.as_ref());
         let resp = builder.send().await.map_err(OsuError::RequestError)?;
 
         Ok(resp)
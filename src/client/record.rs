@@ -0,0 +1,58 @@
+use bytes::Bytes;
+use std::{fs, path::PathBuf};
+
+/// Response bodies stored as JSON files in a directory, one file per route.
+///
+/// Used to record responses of the api once and replay them afterwards
+/// so that tests don't depend on the live api.
+pub(crate) struct Fixtures {
+    dir: PathBuf,
+}
+
+impl Fixtures {
+    pub(crate) fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// The recorded response for the given route, if any.
+    pub(crate) fn replay(&self, route: &str) -> Option<Bytes> {
+        fs::read(self.path(route)).ok().map(Bytes::from)
+    }
+
+    /// Store the response of the given route, creating the directory if necessary.
+    pub(crate) fn record(&self, route: &str, bytes: &[u8]) {
+        let path = self.path(route);
+
+        if let Err(why) = fs::create_dir_all(&self.dir).and_then(|_| fs::write(&path, bytes)) {
+            warn!("Failed to record fixture {:?}: {}", path, why);
+        }
+    }
+
+    /// The route with each non-alphanumeric character replaced by `_`,
+    /// e.g. `get_user?u=2&m=1` is stored as `get_user_u_2_m_1.json`.
+    fn path(&self, route: &str) -> PathBuf {
+        let mut name: String = route
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+
+        name.push_str(".json");
+
+        self.dir.join(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixture_path() {
+        let fixtures = Fixtures::new(PathBuf::from("fixtures"));
+
+        assert_eq!(
+            fixtures.path("get_user?u=2&m=1"),
+            PathBuf::from("fixtures").join("get_user_u_2_m_1.json")
+        );
+    }
+}
@@ -1,6 +1,9 @@
+use base64::DecodeError;
 use reqwest::{Error as ReqwestError, StatusCode};
 use serde::Deserialize;
 use serde_json::Error as JsonError;
+#[cfg(feature = "dedup")]
+use std::sync::Arc;
 use thiserror::Error as ThisError;
 
 /// `Result<_, OsuError>`
@@ -15,18 +18,31 @@ pub enum OsuError {
     BuildingClient(#[source] ReqwestError),
     #[error("Failed to chunk a response")]
     ChunkingResponse(#[source] ReqwestError),
+    #[cfg(feature = "dedup")]
+    #[error("A request that was shared between multiple callers failed")]
+    Deduplicated(#[source] Arc<OsuError>),
     #[error("Failed to parse grade")]
     GradeParsing,
     #[error("Either the specified multiplayer match id was invalid or the match is private")]
     InvalidMultiplayerMatch,
+    #[error("Invalid username `{0}`, must be between 1 and 15 characters")]
+    InvalidUsername(String),
+    #[error("Could not parse `{0}` into GameMode")]
+    ModeParsing(String),
     #[error("Failed to parse mods")]
     ModParsing(#[source] ModError),
+    #[error("The requested resource was not found")]
+    NotFound,
     #[error("Failed to deserialize a response")]
     Parsing {
         body: String,
         #[source]
         source: JsonError,
     },
+    #[error("Failed to decode replay data")]
+    ReplayDecoding(#[source] DecodeError),
+    #[error("The score has no replay available")]
+    ReplayUnavailable,
     #[error("Failed to send a request")]
     RequestError(#[source] ReqwestError),
     #[error("The response contained an error code={status}")]
@@ -36,14 +52,107 @@ pub enum OsuError {
         error: ApiError,
         status: StatusCode,
     },
+    #[cfg(feature = "simd-json")]
+    #[error("Failed to deserialize a response")]
+    SimdParsing {
+        body: String,
+        #[source]
+        source: simd_json::Error,
+    },
     #[error("The API may be temporarily unavailable (received 503)")]
     ServiceUnavailable(Option<String>),
+    #[error("The api key is invalid or lacks permission: {0}")]
+    Unauthorized(String),
+}
+
+impl OsuError {
+    /// A suggested HTTP status code e.g. for web services that respond based on this error.
+    ///
+    /// - `404` if the resource was not found, including invalid or private matches
+    ///   and unavailable replays
+    /// - `401` if the api key was rejected
+    /// - `429` if the api responded with 429
+    /// - `503` if the api is unavailable
+    /// - `504` if a request timed out
+    /// - `400` for invalid arguments such as usernames or mod strings
+    /// - `500` if the client could not be built
+    /// - `502` otherwise, e.g. for unexpected responses or responses that could not be parsed
+    pub fn http_status_hint(&self) -> u16 {
+        match self {
+            Self::NotFound | Self::InvalidMultiplayerMatch | Self::ReplayUnavailable => 404,
+            Self::Unauthorized(_) => 401,
+            Self::Response { status, .. } if *status == StatusCode::TOO_MANY_REQUESTS => 429,
+            Self::ServiceUnavailable(_) => 503,
+            Self::RequestError(err) if err.is_timeout() => 504,
+            Self::InvalidUsername(_)
+            | Self::ModeParsing(_)
+            | Self::ModParsing(_)
+            | Self::GradeParsing => 400,
+            Self::BuildingClient(_) => 500,
+            #[cfg(feature = "dedup")]
+            Self::Deduplicated(err) => err.http_status_hint(),
+            _ => 502,
+        }
+    }
+}
+
+#[cfg(feature = "dedup")]
+impl OsuError {
+    /// Recreate the error of a request that was shared between concurrent callers.
+    ///
+    /// Errors of the api's response are rebuilt so that each caller receives the same variant.
+    /// Errors with a non-cloneable source are wrapped in [`OsuError::Deduplicated`] instead.
+    pub(crate) fn from_shared(err: &Arc<OsuError>) -> Self {
+        match err.as_ref() {
+            Self::NotFound => Self::NotFound,
+            Self::Response {
+                body,
+                error,
+                status,
+            } => Self::Response {
+                body: body.clone(),
+                error: ApiError {
+                    error: error.error.clone(),
+                },
+                status: *status,
+            },
+            Self::ServiceUnavailable(body) => Self::ServiceUnavailable(body.clone()),
+            Self::Unauthorized(msg) => Self::Unauthorized(msg.clone()),
+            _ => Self::Deduplicated(Arc::clone(err)),
+        }
+    }
+}
+
+/// Extension methods for [`OsuResult`]
+pub trait ResultExt<T> {
+    /// Treat [`OsuError::NotFound`] as absence of a value i.e. `Ok(None)`.
+    fn optional(self) -> OsuResult<Option<T>>;
+}
+
+impl<T> ResultExt<T> for OsuResult<T> {
+    #[inline]
+    fn optional(self) -> OsuResult<Option<T>> {
+        match self {
+            Ok(value) => Ok(Some(value)),
+            Err(OsuError::NotFound) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, ThisError)]
 #[error("{error}")]
 pub struct ApiError {
-    error: String,
+    pub(crate) error: String,
+}
+
+impl ApiError {
+    /// Whether the error was caused by an invalid or insufficient api key.
+    pub(crate) fn is_unauthorized(&self, status: StatusCode) -> bool {
+        status == StatusCode::UNAUTHORIZED
+            || status == StatusCode::FORBIDDEN
+            || self.error.to_lowercase().contains("api key")
+    }
 }
 
 #[derive(Debug, ThisError)]
@@ -53,3 +162,62 @@ pub enum ModError {
     #[error("Failed to parse string")]
     Str,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_http_status_hint() {
+        let response = |status| OsuError::Response {
+            body: String::new(),
+            error: ApiError {
+                error: String::new(),
+            },
+            status,
+        };
+
+        let parsing = OsuError::Parsing {
+            body: String::new(),
+            source: serde_json::from_str::<u32>("").unwrap_err(),
+        };
+
+        assert_eq!(OsuError::NotFound.http_status_hint(), 404);
+        assert_eq!(OsuError::InvalidMultiplayerMatch.http_status_hint(), 404);
+        assert_eq!(OsuError::ReplayUnavailable.http_status_hint(), 404);
+        assert_eq!(
+            OsuError::Unauthorized(String::new()).http_status_hint(),
+            401
+        );
+        assert_eq!(
+            response(StatusCode::TOO_MANY_REQUESTS).http_status_hint(),
+            429
+        );
+        assert_eq!(OsuError::ServiceUnavailable(None).http_status_hint(), 503);
+        assert_eq!(
+            OsuError::InvalidUsername(String::new()).http_status_hint(),
+            400
+        );
+        assert_eq!(OsuError::ModParsing(ModError::Str).http_status_hint(), 400);
+        assert_eq!(OsuError::GradeParsing.http_status_hint(), 400);
+        assert_eq!(OsuError::ModeParsing(String::new()).http_status_hint(), 400);
+        assert_eq!(parsing.http_status_hint(), 502);
+        assert_eq!(
+            response(StatusCode::INTERNAL_SERVER_ERROR).http_status_hint(),
+            502
+        );
+        assert_eq!(OsuError::ApprovalStatusParsing(9).http_status_hint(), 502);
+    }
+
+    #[test]
+    fn result_optional() {
+        let not_found: OsuResult<u32> = Err(OsuError::NotFound);
+        assert!(matches!(not_found.optional(), Ok(None)));
+
+        let found: OsuResult<u32> = Ok(2);
+        assert!(matches!(found.optional(), Ok(Some(2))));
+
+        let other: OsuResult<u32> = Err(OsuError::GradeParsing);
+        assert!(matches!(other.optional(), Err(OsuError::GradeParsing)));
+    }
+}
@@ -4,7 +4,7 @@
 //!
 //! rosu is a rust wrapper for the [osu!api **v1**](https://github.com/ppy/osu-api/wiki) .
 //!
-//! The wrapper provides access to the beatmap, user, score, user-best, user-recent, and match endpoints.
+//! The wrapper provides access to the beatmap, user, score, user-best, user-recent, match, and replay endpoints.
 //!
 //! **Note:** Only the osu!api v1 is supported. If you want to use v2, check out [rosu-v2](https://github.com/MaxOhn/rosu-v2).
 //!
@@ -67,6 +67,12 @@
 //! | ----------- | ------------------------------------------------------ | --------------------------------------------------- |
 //! | `serialize` | Provides serialization for all types in the `model` module | [serde-repr](https://github.com/dtolnay/serde-repr) |
 //! | `metrics`   | Make the client count each request type and enable a method on the client to get a `prometheus::IntCounterVec` | [prometheus](https://github.com/tikv/rust-prometheus)
+//! | `dedup`     | Concurrent identical requests share a single response instead of each hitting the api |
+//! | `mem-cache` | Cache user and beatmap responses in memory for a duration set via `OsuBuilder::cache_ttl` |
+//! | `simd-json` | Deserialize responses with `simd-json` instead of `serde_json` | [simd-json](https://github.com/simd-lite/simd-json)
+//! | `tracing`   | Wrap each request in a `tracing` span that records the endpoint and the outcome | [tracing](https://github.com/tokio-rs/tracing)
+//! | `record`    | Record responses as JSON fixtures in a directory set via `OsuBuilder::fixture_dir` and replay them afterwards |
+//! | `chrono`    | Provides conversions between the `time` dates of the `model` module and `chrono` dates | [chrono](https://github.com/chronotope/chrono)
 //!
 
 #![deny(clippy::all, nonstandard_style, rust_2018_idioms, unused, warnings)]
@@ -76,6 +82,12 @@ extern crate log;
 #[macro_use]
 extern crate bitflags;
 
+#[cfg(feature = "mem-cache")]
+/// Contains the in-memory cache for responses
+pub(crate) mod cache;
+#[cfg(feature = "chrono")]
+/// Contains conversions between `time` and `chrono` dates
+pub mod chrono_compat;
 /// Contains the Osu client
 mod client;
 /// Contains any kind of OsuError that can occur
@@ -96,6 +108,8 @@ mod routing;
 /// Contains methods and implementations to (de)serialize structs
 pub(crate) mod serde;
 
-pub use error::{OsuError, OsuResult};
+pub use error::{OsuError, OsuResult, ResultExt};
 
 pub use client::{Osu, OsuBuilder};
+
+pub use ratelimit::RatelimitInfo;
@@ -5,24 +5,29 @@ pub(crate) struct Metrics {
     pub(crate) beatmaps: IntCounter,
     pub(crate) matches: IntCounter,
     pub(crate) recent_scores: IntCounter,
+    pub(crate) replays: IntCounter,
     pub(crate) scores: IntCounter,
     pub(crate) top_scores: IntCounter,
     pub(crate) users: IntCounter,
 }
 
 impl Metrics {
+    /// Label value of the `client` label if no client name was specified.
+    pub(crate) const DEFAULT_CLIENT: &'static str = "rosu";
+
     #[rustfmt::skip]
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(client: &str) -> Self {
         let opts = Opts::new("osu_requests", "osu!api request count");
-        let counters = IntCounterVec::new(opts, &["type"]).unwrap();
+        let counters = IntCounterVec::new(opts, &["type", "client"]).unwrap();
 
         Self {
-            beatmaps: counters.get_metric_with_label_values(&["Beatmaps"]).unwrap(),
-            matches: counters.get_metric_with_label_values(&["Matches"]).unwrap(),
-            recent_scores: counters.get_metric_with_label_values(&["RecentScores"]).unwrap(),
-            scores: counters.get_metric_with_label_values(&["Scores"]).unwrap(),
-            top_scores: counters.get_metric_with_label_values(&["TopScores"]).unwrap(),
-            users: counters.get_metric_with_label_values(&["Users"]).unwrap(),
+            beatmaps: counters.get_metric_with_label_values(&["Beatmaps", client]).unwrap(),
+            matches: counters.get_metric_with_label_values(&["Matches", client]).unwrap(),
+            recent_scores: counters.get_metric_with_label_values(&["RecentScores", client]).unwrap(),
+            replays: counters.get_metric_with_label_values(&["Replays", client]).unwrap(),
+            scores: counters.get_metric_with_label_values(&["Scores", client]).unwrap(),
+            top_scores: counters.get_metric_with_label_values(&["TopScores", client]).unwrap(),
+            users: counters.get_metric_with_label_values(&["Users", client]).unwrap(),
 
             counters,
         }
@@ -1,6 +1,6 @@
 use crate::{
-    model::GameMode,
-    request::{GetScores, GetUser},
+    model::{GameMode, GameMods},
+    request::{GetScore, GetScores, GetUser},
     serde::*,
     Osu, OsuError,
 };
@@ -18,8 +18,12 @@ use serde::Serialize;
 use serde_repr::Serialize_repr;
 
 /// Beatmap struct retrieved from the `/api/get_beatmaps` endpoint.
+///
+/// Marked as `#[non_exhaustive]` so that fields can be added without a breaking change.
+/// Create beatmaps through [`Beatmap::new`] and set further fields afterwards.
 #[derive(Debug, Clone, Deserialize)]
 #[cfg_attr(feature = "serialize", derive(Serialize))]
+#[non_exhaustive]
 pub struct Beatmap {
     #[serde(alias = "approved")]
     pub approval_status: ApprovalStatus,
@@ -120,11 +124,30 @@ pub struct Beatmap {
     pub audio_unavailable: bool,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub file_md5: Option<String>,
+    /// Whether the beatmap was converted from `GameMode::Osu` into its current mode.
+    ///
+    /// The api does not flag converted maps and their `mode` is the requested one
+    /// so this is only inferred from the request.
+    /// `Some(false)` if converted maps were not requested, or could not be returned
+    /// i.e. the mode was `GameMode::Osu` or unspecified.
+    /// `None` if converted maps were requested for another mode because
+    /// they can not be told apart from maps native to that mode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub converted: Option<bool>,
 }
 
+/// Formats as `artist - title [version]`.
+///
+/// The alternate flag `{:#}` appends the mode and star rating, e.g. `(osu, 5.30★)`.
 impl Display for Beatmap {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        write!(f, "{} - {} [{}]", self.artist, self.title, self.version)
+        write!(f, "{} - {} [{}]", self.artist, self.title, self.version)?;
+
+        if f.alternate() {
+            write!(f, " ({}, {:.2}★)", self.mode, self.stars)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -164,6 +187,7 @@ impl Default for Beatmap {
             download_unavailable: true,
             audio_unavailable: true,
             file_md5: None,
+            converted: None,
             approval_status: ApprovalStatus::WIP,
             submit_date: OffsetDateTime::now_utc(),
             approved_date: None,
@@ -173,6 +197,23 @@ impl Default for Beatmap {
 }
 
 impl Beatmap {
+    /// Create a new beatmap with the given beatmap and mapset id.
+    ///
+    /// All other fields are set to their default.
+    pub fn new(beatmap_id: u32, beatmapset_id: u32) -> Self {
+        Self {
+            beatmap_id,
+            beatmapset_id,
+            ..Default::default()
+        }
+    }
+
+    /// The osu!api endpoint that beatmaps are retrieved from.
+    #[inline]
+    pub fn source_endpoint() -> &'static str {
+        "get_beatmaps"
+    }
+
     /// Retrieve the creator of the beatmap from the API.
     /// Be sure to specify [`GameMode`] if necessary, defaults to `Osu`.
     pub fn get_creator<'o>(&self, osu: &'o Osu) -> GetUser<'o> {
@@ -185,10 +226,151 @@ impl Beatmap {
         osu.scores(self.beatmap_id).mode(self.mode)
     }
 
+    /// Retrieve the global #1 score of the beatmap from the API,
+    /// `None` if the leaderboard is empty.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use rosu::{Osu, OsuResult};
+    /// # #[tokio::main]
+    /// # async fn main() -> OsuResult<()> {
+    /// # let osu = Osu::new("osu_api_key");
+    /// if let Some(map) = osu.beatmap().map_id(905576).await? {
+    ///     if let Some(score) = map.get_leader(&osu).await? {
+    ///         println!("{} leads with {}", score.user_id, score.score);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_leader<'o>(&self, osu: &'o Osu) -> GetScore<'o> {
+        osu.score(self.beatmap_id).mode(self.mode).limit(1)
+    }
+
+    /// Retrieve the global top scores of the beatmap whose replay is available.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use rosu::{Osu, OsuResult};
+    /// # #[tokio::main]
+    /// # async fn main() -> OsuResult<()> {
+    /// # let osu = Osu::new("osu_api_key");
+    /// if let Some(map) = osu.beatmap().map_id(905576).await? {
+    ///     for score in map.get_replay_scores(&osu).limit(100).await? {
+    ///         // download replay of the score
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_replay_scores<'o>(&self, osu: &'o Osu) -> GetScores<'o> {
+        self.get_global_leaderboard(osu).with_replays_only()
+    }
+
+    /// The url of the mapset's cover image.
+    #[inline]
+    pub fn cover_url(&self) -> String {
+        format!(
+            "https://assets.ppy.sh/beatmaps/{}/covers/cover.jpg",
+            self.beatmapset_id
+        )
+    }
+
+    /// The url of the mapset's thumbnail image.
+    #[inline]
+    pub fn thumbnail_url(&self) -> String {
+        format!("https://b.ppy.sh/thumb/{}l.jpg", self.beatmapset_id)
+    }
+
+    /// The url of the mapset's website.
+    #[inline]
+    pub fn mapset_url(&self) -> String {
+        format!("https://osu.ppy.sh/s/{}", self.beatmapset_id)
+    }
+
+    /// The url of the beatmap's website.
+    #[inline]
+    pub fn map_url(&self) -> String {
+        format!("https://osu.ppy.sh/b/{}", self.beatmap_id)
+    }
+
+    /// The url to download the mapset.
+    #[inline]
+    pub fn download_url(&self) -> String {
+        format!("https://osu.ppy.sh/d/{}", self.beatmapset_id)
+    }
+
     /// Count all circles, sliders, and spinners of the beatmap
     pub fn count_objects(&self) -> u32 {
         self.count_circle + self.count_slider + self.count_spinner
     }
+
+    /// The amount of objects per second of drain time, `0.0` if the drain time is zero.
+    pub fn objects_per_second(&self) -> f32 {
+        if self.seconds_drain == 0 {
+            return 0.0;
+        }
+
+        self.count_objects() as f32 / self.seconds_drain as f32
+    }
+
+    /// The amount of objects per second of drain time when played with the given mods,
+    /// see [`GameMods::clock_rate`].
+    pub fn objects_per_second_with_mods(&self, mods: GameMods) -> f32 {
+        self.objects_per_second() * mods.clock_rate()
+    }
+
+    /// Returns the max combo of the beatmap if available.
+    ///
+    /// Otherwise, for `GameMode::Osu` the max combo is approximated by assuming one combo
+    /// per circle and spinner and two combo per slider i.e. sliders without ticks or repeats.
+    /// For all other modes, no estimation is made.
+    pub fn estimated_max_combo(&self) -> Option<u32> {
+        match (self.max_combo, self.mode) {
+            (Some(combo), _) => Some(combo),
+            (None, GameMode::Osu) => {
+                Some(self.count_circle + 2 * self.count_slider + self.count_spinner)
+            }
+            (None, _) => None,
+        }
+    }
+
+    /// The amount of full days since the beatmap was approved e.g. ranked or loved,
+    /// `None` if it has no approved date.
+    pub fn days_since_ranked(&self) -> Option<i64> {
+        self.approved_date
+            .map(|date| (OffsetDateTime::now_utc() - date).whole_days())
+    }
+
+    /// Whether the beatmap was approved e.g. ranked or loved within the given duration.
+    ///
+    /// Always `false` if it has no approved date.
+    pub fn is_recently_ranked(&self, within: time::Duration) -> bool {
+        matches!(self.approved_date, Some(date) if OffsetDateTime::now_utc() - date <= within)
+    }
+
+    /// The individual tags of the beatmap.
+    pub fn tag_list(&self) -> Vec<&str> {
+        self.tags.split_whitespace().collect()
+    }
+
+    #[cfg(feature = "chrono")]
+    /// The submit date of the beatmap as `chrono` date.
+    pub fn submit_date_chrono(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::chrono_compat::to_chrono(self.submit_date)
+    }
+
+    #[cfg(feature = "chrono")]
+    /// The approved date of the beatmap as `chrono` date.
+    pub fn approved_date_chrono(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.approved_date.map(crate::chrono_compat::to_chrono)
+    }
+
+    #[cfg(feature = "chrono")]
+    /// The last update date of the beatmap as `chrono` date.
+    pub fn last_update_chrono(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::chrono_compat::to_chrono(self.last_update)
+    }
 }
 
 impl PartialEq for Beatmap {
@@ -301,6 +483,8 @@ impl From<u8> for Language {
 }
 
 /// Basic enum to describe a [`Beatmap`]'s approval status
+///
+/// Unknown values, e.g. statuses added to the api later on, are deserialized as `Pending`.
 #[derive(Debug, Hash, Clone, Copy, Eq, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(Serialize_repr))]
 #[repr(i8)]
@@ -336,20 +520,229 @@ impl TryFrom<i8> for ApprovalStatus {
 mod tests {
     use super::*;
 
+    #[test]
+    fn beatmap_new() {
+        let map = Beatmap::new(2271405, 1086483);
+        assert_eq!(map.beatmap_id, 2271405);
+        assert_eq!(map.beatmapset_id, 1086483);
+        assert_eq!(map.mode, GameMode::Osu);
+        assert_eq!(map.max_combo, None);
+    }
+
     #[test]
     fn beatmap_display() {
-        let mut map = Beatmap::default();
-        map.title = "title".to_owned();
-        map.artist = "artist".to_owned();
+        let map = Beatmap {
+            title: "title".to_owned(),
+            artist: "artist".to_owned(),
+            ..Default::default()
+        };
         assert_eq!(map.to_string(), "artist - title []");
+
+        let map = Beatmap {
+            version: "Insane".to_owned(),
+            mode: GameMode::Taiko,
+            stars: 5.3048,
+            ..map
+        };
+        assert_eq!(map.to_string(), "artist - title [Insane]");
+        assert_eq!(
+            format!("{:#}", map),
+            "artist - title [Insane] (taiko, 5.30★)"
+        );
+    }
+
+    #[test]
+    fn beatmap_ranked_age() {
+        let mut map = Beatmap::new(2271405, 1086483);
+        assert_eq!(map.days_since_ranked(), None);
+        assert!(!map.is_recently_ranked(time::Duration::days(365)));
+
+        map.approved_date = Some(OffsetDateTime::now_utc() - time::Duration::hours(10 * 24 + 1));
+        assert_eq!(map.days_since_ranked(), Some(10));
+        assert!(map.is_recently_ranked(time::Duration::days(11)));
+        assert!(!map.is_recently_ranked(time::Duration::days(7)));
+    }
+
+    #[test]
+    fn beatmap_urls() {
+        let map = Beatmap::new(2271405, 1086483);
+        assert_eq!(
+            map.cover_url(),
+            "https://assets.ppy.sh/beatmaps/1086483/covers/cover.jpg"
+        );
+        assert_eq!(map.thumbnail_url(), "https://b.ppy.sh/thumb/1086483l.jpg");
+        assert_eq!(map.mapset_url(), "https://osu.ppy.sh/s/1086483");
+        assert_eq!(map.map_url(), "https://osu.ppy.sh/b/2271405");
+        assert_eq!(map.download_url(), "https://osu.ppy.sh/d/1086483");
+    }
+
+    #[test]
+    fn beatmap_objects_per_second() {
+        let mut map = Beatmap {
+            count_circle: 300,
+            count_slider: 195,
+            count_spinner: 5,
+            seconds_drain: 100,
+            ..Default::default()
+        };
+        assert!((map.objects_per_second() - 5.0).abs() <= f32::EPSILON);
+
+        let dt = map.objects_per_second_with_mods(GameMods::Hidden | GameMods::DoubleTime);
+        assert!((dt - 7.5).abs() <= f32::EPSILON);
+
+        let ht = map.objects_per_second_with_mods(GameMods::HalfTime);
+        assert!((ht - 3.75).abs() <= f32::EPSILON);
+
+        map.seconds_drain = 0;
+        assert!(map.objects_per_second().abs() <= f32::EPSILON);
+        assert!(map.objects_per_second_with_mods(GameMods::DoubleTime).abs() <= f32::EPSILON);
     }
 
     #[test]
     fn beatmap_count_objects() {
-        let mut map = Beatmap::default();
-        map.count_circle = 456;
-        map.count_slider = 42;
-        map.count_spinner = 1;
+        let map = Beatmap {
+            count_circle: 456,
+            count_slider: 42,
+            count_spinner: 1,
+            ..Default::default()
+        };
         assert_eq!(map.count_objects(), 456 + 42 + 1);
     }
+
+    #[test]
+    fn beatmap_tag_list() {
+        let map = Beatmap {
+            tags: " touhou  bad apple\tshadow art ".to_owned(),
+            ..Default::default()
+        };
+        assert_eq!(
+            map.tag_list(),
+            vec!["touhou", "bad", "apple", "shadow", "art"]
+        );
+        assert!(Beatmap::default().tag_list().is_empty());
+    }
+
+    #[test]
+    fn beatmap_estimated_max_combo() {
+        let mut map = Beatmap {
+            count_circle: 456,
+            count_slider: 42,
+            count_spinner: 1,
+            ..Default::default()
+        };
+        assert_eq!(map.estimated_max_combo(), Some(456 + 2 * 42 + 1));
+
+        map.max_combo = Some(789);
+        assert_eq!(map.estimated_max_combo(), Some(789));
+
+        map.max_combo = None;
+        map.mode = GameMode::Mania;
+        assert_eq!(map.estimated_max_combo(), None);
+    }
+
+    #[test]
+    fn beatmap_without_file_md5() {
+        let json = r#"{
+            "beatmapset_id": "1086483",
+            "beatmap_id": "2271404",
+            "approved": "1",
+            "total_length": "167",
+            "hit_length": "164",
+            "version": "Insane",
+            "diff_size": "4",
+            "diff_overall": "8",
+            "diff_approach": "9",
+            "diff_drain": "5",
+            "mode": "0",
+            "count_normal": "464",
+            "count_slider": "229",
+            "count_spinner": "1",
+            "submit_date": "2019-12-23 11:01:42",
+            "approved_date": "2020-02-06 16:40:23",
+            "last_update": "2020-01-29 12:51:02",
+            "artist": "Artist",
+            "title": "Title",
+            "creator": "Mao",
+            "creator_id": "3178418",
+            "bpm": "174",
+            "source": "",
+            "tags": "",
+            "genre_id": "2",
+            "language_id": "3",
+            "favourite_count": "84",
+            "rating": "9.26",
+            "storyboard": "0",
+            "video": "0",
+            "download_unavailable": "0",
+            "audio_unavailable": "0",
+            "playcount": "98760",
+            "passcount": "10983",
+            "max_combo": "1063",
+            "diff_aim": "2.6457",
+            "diff_speed": "2.3561",
+            "difficultyrating": "5.3048"
+        }"#;
+
+        let map: Beatmap = serde_json::from_str(json).unwrap();
+        assert_eq!(map.beatmap_id, 2_271_404);
+        assert_eq!(map.file_md5, None);
+    }
+
+    #[test]
+    fn beatmap_unknown_approval_status() {
+        let json = r#"{
+            "beatmapset_id": "1086483",
+            "beatmap_id": "2271404",
+            "approved": "7",
+            "total_length": "167",
+            "hit_length": "164",
+            "version": "Insane",
+            "diff_size": "4",
+            "diff_overall": "8",
+            "diff_approach": "9",
+            "diff_drain": "5",
+            "mode": "0",
+            "count_normal": "464",
+            "count_slider": "229",
+            "count_spinner": "1",
+            "submit_date": "2019-12-23 11:01:42",
+            "approved_date": null,
+            "last_update": "2020-01-29 12:51:02",
+            "artist": "Artist",
+            "title": "Title",
+            "creator": "Mao",
+            "creator_id": "3178418",
+            "bpm": "174",
+            "source": "",
+            "tags": "",
+            "genre_id": "2",
+            "language_id": "3",
+            "favourite_count": "84",
+            "rating": "9.26",
+            "storyboard": "0",
+            "video": "0",
+            "download_unavailable": "0",
+            "audio_unavailable": "0",
+            "playcount": "98760",
+            "passcount": "10983",
+            "max_combo": "1063",
+            "diff_aim": "2.6457",
+            "diff_speed": "2.3561",
+            "difficultyrating": "5.3048"
+        }"#;
+
+        let map: Beatmap = serde_json::from_str(json).unwrap();
+        assert_eq!(map.approval_status, ApprovalStatus::Pending);
+
+        let status: ApprovalStatus = serde_json::from_str("-3").unwrap();
+        assert_eq!(status, ApprovalStatus::Pending);
+        let status: ApprovalStatus = serde_json::from_str("\"wip\"").unwrap();
+        assert_eq!(status, ApprovalStatus::WIP);
+        assert!(serde_json::from_str::<ApprovalStatus>("\"unknown\"").is_err());
+    }
+
+    #[test]
+    fn beatmap_source_endpoint() {
+        assert_eq!(Beatmap::source_endpoint(), "get_beatmaps");
+    }
 }
@@ -0,0 +1,132 @@
+use crate::model::{GameMods, Grade, Score};
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+/// Smaller representation of a [`Score`] e.g. to store lots of scores in a database.
+///
+/// Mods are stored as their `u32` value and the date as unix timestamp.
+///
+/// The conversion from [`Score`] is lossy:
+///   - `username`, `replay_available`, and `mode` are dropped and will be `None` when converting back.
+///   - The date only keeps whole seconds.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct CompactScore {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub beatmap_id: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score_id: Option<u64>,
+    pub score: u32,
+    pub user_id: u32,
+    pub count300: u32,
+    pub count100: u32,
+    pub count50: u32,
+    pub count_miss: u32,
+    pub count_geki: u32,
+    pub count_katu: u32,
+    pub max_combo: u32,
+    pub perfect: bool,
+    pub mods: u32,
+    pub date: i64,
+    pub grade: Grade,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pp: Option<f32>,
+}
+
+impl From<Score> for CompactScore {
+    fn from(score: Score) -> Self {
+        Self {
+            beatmap_id: score.beatmap_id,
+            score_id: score.score_id,
+            score: score.score,
+            user_id: score.user_id,
+            count300: score.count300,
+            count100: score.count100,
+            count50: score.count50,
+            count_miss: score.count_miss,
+            count_geki: score.count_geki,
+            count_katu: score.count_katu,
+            max_combo: score.max_combo,
+            perfect: score.perfect,
+            mods: score.enabled_mods.bits(),
+            date: score.date.unix_timestamp(),
+            grade: score.grade,
+            pp: score.pp,
+        }
+    }
+}
+
+impl From<CompactScore> for Score {
+    fn from(score: CompactScore) -> Self {
+        Self {
+            beatmap_id: score.beatmap_id,
+            score_id: score.score_id,
+            score: score.score,
+            user_id: score.user_id,
+            username: None,
+            count300: score.count300,
+            count100: score.count100,
+            count50: score.count50,
+            count_miss: score.count_miss,
+            count_geki: score.count_geki,
+            count_katu: score.count_katu,
+            max_combo: score.max_combo,
+            perfect: score.perfect,
+            enabled_mods: GameMods::from_bits_partial(score.mods).0,
+            date: OffsetDateTime::from_unix_timestamp(score.date)
+                .unwrap_or(OffsetDateTime::UNIX_EPOCH),
+            grade: score.grade,
+            pp: score.pp,
+            replay_available: None,
+            mode: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compact_score_round_trip() {
+        let score = Score {
+            beatmap_id: Some(905576),
+            score_id: Some(2_177_560_145),
+            score: 1_000_000,
+            user_id: 2,
+            username: Some("Badewanne3".to_owned()),
+            count300: 500,
+            count100: 20,
+            max_combo: 700,
+            enabled_mods: GameMods::Hidden | GameMods::NightCore,
+            date: OffsetDateTime::from_unix_timestamp(1_588_336_496).unwrap(),
+            grade: Grade::A,
+            pp: Some(123.45),
+            replay_available: Some(true),
+            ..Default::default()
+        };
+
+        let compact = CompactScore::from(score.clone());
+        assert_eq!(compact.mods, 8 + 64 + 512);
+
+        let serialized = serde_json::to_string(&compact).unwrap();
+        let deserialized: CompactScore = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, compact);
+
+        let restored = Score::from(deserialized);
+        assert_eq!(restored.beatmap_id, score.beatmap_id);
+        assert_eq!(restored.score_id, score.score_id);
+        assert_eq!(restored.score, score.score);
+        assert_eq!(restored.count300, score.count300);
+        assert_eq!(restored.count100, score.count100);
+        assert_eq!(restored.max_combo, score.max_combo);
+        assert_eq!(restored.enabled_mods, score.enabled_mods);
+        assert_eq!(restored.date, score.date);
+        assert_eq!(restored.grade, score.grade);
+        assert_eq!(restored.pp, score.pp);
+
+        // Lossy fields
+        assert_eq!(restored.username, None);
+        assert_eq!(restored.replay_available, None);
+    }
+}
@@ -0,0 +1,66 @@
+use crate::model::{GameMode, Grade, Score};
+
+use serde::{Deserialize, Serialize};
+
+/// A [`Score`] together with values computed for a given mode, see [`Score::to_enriched`].
+///
+/// Serializes as the score's fields plus `accuracy` and `calculated_grade`.
+/// The latter is the recalculated grade, as opposed to the score's `grade` provided by the api.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct EnrichedScore {
+    #[serde(flatten)]
+    pub score: Score,
+    pub accuracy: f32,
+    pub calculated_grade: Grade,
+}
+
+impl Score {
+    /// Pair the score with its accuracy and recalculated grade in the given mode
+    /// so that consumers of the serialized score need not compute them.
+    pub fn to_enriched(&self, mode: GameMode) -> EnrichedScore {
+        let accuracy = self.accuracy(mode);
+        let calculated_grade = self.clone().recalculate_grade(mode, Some(accuracy));
+
+        EnrichedScore {
+            score: self.clone(),
+            accuracy,
+            calculated_grade,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::GameMods;
+    use time::OffsetDateTime;
+
+    #[test]
+    fn enriched_score_round_trip() {
+        let mut score = Score::new(2, 1_000_000);
+        score.beatmap_id = Some(905576);
+        score.count300 = 500;
+        score.count100 = 20;
+        score.count_miss = 1;
+        score.max_combo = 700;
+        score.enabled_mods = GameMods::Hidden | GameMods::HardRock;
+        score.date = OffsetDateTime::from_unix_timestamp(1_588_336_496).unwrap();
+        score.grade = Grade::F;
+        score.pp = Some(123.45);
+
+        let enriched = score.to_enriched(GameMode::Osu);
+        assert!((enriched.accuracy - 97.25).abs() < f32::EPSILON);
+        assert_eq!(enriched.calculated_grade, Grade::A);
+        assert_eq!(enriched.score.grade, Grade::F);
+
+        let serialized = serde_json::to_string(&enriched).unwrap();
+        assert!(serialized.contains(r#""accuracy":97.25"#));
+        assert!(serialized.contains(r#""calculated_grade":"A""#));
+
+        let deserialized: EnrichedScore = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, enriched);
+        assert_eq!(deserialized.score.count300, 500);
+        assert_eq!(deserialized.score.enabled_mods, score.enabled_mods);
+        assert_eq!(deserialized.score.pp, score.pp);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    model::{GameMode, GameMods},
+    model::{score::HitCounts, GameMode, GameMods},
     serde::*,
 };
 
@@ -8,7 +8,9 @@ use serde::{
     Deserialize, Deserializer,
 };
 use std::{
-    fmt::{Formatter, Result as FmtResult},
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    fmt::{Display, Formatter, Result as FmtResult},
     hash::Hash,
 };
 use time::{OffsetDateTime, PrimitiveDateTime};
@@ -34,6 +36,103 @@ pub struct Match {
     pub games: Vec<MatchGame>,
 }
 
+impl Match {
+    /// The osu!api endpoint that matches are retrieved from.
+    #[inline]
+    pub fn source_endpoint() -> &'static str {
+        "get_match"
+    }
+
+    /// Combine two matches e.g. of a lobby that was restarted with a new id.
+    ///
+    /// The games of `other` are appended to the games of `self`, skipping
+    /// games whose id is already contained. The start time is the earliest
+    /// of both and the end time is the latest of both, or `None` if either
+    /// match did not end yet. Match id and name are taken from `self`.
+    pub fn merge(&self, other: &Match) -> Match {
+        let mut games = self.games.clone();
+
+        for game in other.games.iter() {
+            if games.iter().all(|g| g.game_id != game.game_id) {
+                games.push(game.clone());
+            }
+        }
+
+        Match {
+            match_id: self.match_id,
+            name: self.name.clone(),
+            start_time: self.start_time.min(other.start_time),
+            end_time: self.end_time.zip(other.end_time).map(|(a, b)| a.max(b)),
+            games,
+        }
+    }
+
+    /// The games of the match sorted by their start time.
+    ///
+    /// The api generally returns games in order already but this guarantees it.
+    /// Games with the same start time keep their relative order.
+    pub fn games_chronological(&self) -> Vec<&MatchGame> {
+        let mut games: Vec<_> = self.games.iter().collect();
+        games.sort_by_key(|game| game.start_time);
+
+        games
+    }
+
+    /// The game ids between the smallest and largest game id of the match that
+    /// are not contained, sorted ascendingly.
+    ///
+    /// The api does not return aborted games so the gaps approximate them.
+    /// This is only a heuristic since game ids are assigned globally and thus
+    /// games of other lobbies may lie in between, resulting in many ids.
+    pub fn game_id_gaps(&self) -> Vec<u32> {
+        let ids: HashSet<_> = self.games.iter().map(|game| game.game_id).collect();
+
+        let (min, max) = match (ids.iter().min(), ids.iter().max()) {
+            (Some(&min), Some(&max)) => (min, max),
+            _ => return Vec::new(),
+        };
+
+        (min..max).filter(|id| !ids.contains(id)).collect()
+    }
+
+    /// Aggregate the scores of all games per user id.
+    ///
+    /// See [`MatchGame::winners`] for how the winners of a game are determined.
+    pub fn user_stats(&self) -> HashMap<u32, MatchUserStats> {
+        let mut stats: HashMap<u32, MatchUserStats> = HashMap::new();
+
+        for game in self.games.iter() {
+            let winners = game.winners();
+
+            for score in game.scores.iter() {
+                let entry = stats.entry(score.user_id).or_default();
+                entry.total_score += score.score as u64;
+                entry.average_accuracy += score.accuracy(game.mode);
+                entry.games_played += 1;
+                entry.wins += winners.contains(&score.user_id) as u32;
+            }
+        }
+
+        for entry in stats.values_mut() {
+            entry.average_accuracy /= entry.games_played as f32;
+        }
+
+        stats
+    }
+
+    #[cfg(feature = "chrono")]
+    /// The start time of the match as `chrono` date.
+    pub fn start_time_chrono(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::chrono_compat::to_chrono(self.start_time)
+    }
+
+    #[cfg(feature = "chrono")]
+    /// The end time of the match as `chrono` date.
+    pub fn end_time_chrono(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.end_time.map(crate::chrono_compat::to_chrono)
+    }
+}
+
 impl<'de> Deserialize<'de> for Match {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -103,7 +202,9 @@ impl<'de> Deserialize<'de> for Match {
                         games,
                     },
                     None => {
-                        let Some(((match_id, name), start_time)) = match_id.zip(name).zip(start_time) else {
+                        let Some(((match_id, name), start_time)) =
+                            match_id.zip(name).zip(start_time)
+                        else {
                             return Err(Error::custom(
                                 "Deserializing Match requires either the field `match`, \
                                 or the fields `match_id`, `name`, and `start_time`",
@@ -162,7 +263,11 @@ pub struct MatchGame {
     pub game_id: u32,
     #[serde(with = "serde_date")]
     pub start_time: OffsetDateTime,
-    #[serde(with = "serde_maybe_date", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        with = "serde_maybe_date",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub end_time: Option<OffsetDateTime>,
     #[serde(deserialize_with = "to_u32")]
     pub beatmap_id: u32,
@@ -179,6 +284,69 @@ pub struct MatchGame {
     pub scores: Vec<GameScore>,
 }
 
+impl MatchGame {
+    /// The user ids of all winners of the game.
+    ///
+    /// For team games, these are all members of the team with the higher
+    /// total score. Otherwise, these are the users with the highest score,
+    /// accuracy, or max combo, depending on the game's [`ScoringType`].
+    ///
+    /// Ties between teams produce no winners, and neither do team games
+    /// with [`ScoringType::Accuracy`] or [`ScoringType::Combo`].
+    pub fn winners(&self) -> Vec<u32> {
+        match self.team_type {
+            TeamType::TeamVS | TeamType::TagTeamVS => {
+                if matches!(
+                    self.scoring_type,
+                    ScoringType::Accuracy | ScoringType::Combo
+                ) {
+                    return Vec::new();
+                }
+
+                let (blue, red) = self
+                    .scores
+                    .iter()
+                    .fold((0_u64, 0_u64), |(blue, red), score| match score.team {
+                        Team::Blue => (blue + score.score as u64, red),
+                        Team::Red => (blue, red + score.score as u64),
+                        Team::None => (blue, red),
+                    });
+
+                let team = match blue.cmp(&red) {
+                    Ordering::Greater => Team::Blue,
+                    Ordering::Less => Team::Red,
+                    Ordering::Equal => return Vec::new(),
+                };
+
+                self.scores
+                    .iter()
+                    .filter(|score| score.team == team)
+                    .map(|score| score.user_id)
+                    .collect()
+            }
+            TeamType::HeadToHead | TeamType::TagCoop => {
+                let max = self.scores.iter().map(|score| self.rank_key(score)).max();
+
+                self.scores
+                    .iter()
+                    .filter(|score| Some(self.rank_key(score)) == max)
+                    .map(|score| score.user_id)
+                    .collect()
+            }
+        }
+    }
+
+    /// The value by which a score is ranked according to the game's [`ScoringType`].
+    fn rank_key(&self, score: &GameScore) -> u64 {
+        match self.scoring_type {
+            ScoringType::Score | ScoringType::ScoreV2 => score.score as u64,
+            // The accuracy is rounded to two decimals already
+            ScoringType::Accuracy => (score.accuracy(self.mode) * 100.0).round() as u64,
+            ScoringType::Combo => score.max_combo as u64,
+        }
+    }
+}
+
 /// Each participating user of a [`MatchGame`] will produce a [`GameScore`]
 /// which contains the data about the user's play
 #[derive(Debug, Clone, Hash, Deserialize, Eq, PartialEq)]
@@ -217,6 +385,36 @@ pub struct GameScore {
     pub enabled_mods: Option<GameMods>,
 }
 
+impl GameScore {
+    /// The mods the score was played with i.e. the union of the game's
+    /// forced mods and the score's own mods, e.g. in a freemod lobby.
+    pub fn effective_mods(&self, game: &MatchGame) -> GameMods {
+        game.mods.unwrap_or_default() | self.enabled_mods.unwrap_or_default()
+    }
+
+    /// Count all hitobjects of the score i.e. for `GameMode::Osu` the amount 300s, 100s, 50s, and misses.
+    pub fn total_hits(&self, mode: GameMode) -> u32 {
+        HitCounts::from(self).total_hits(mode)
+    }
+
+    /// Calculate the accuracy i.e. `0 <= accuracy <= 100`, the same way as [`Score::accuracy`].
+    ///
+    /// [`Score::accuracy`]: crate::model::Score::accuracy
+    pub fn accuracy(&self, mode: GameMode) -> f32 {
+        HitCounts::from(self).accuracy(mode)
+    }
+}
+
+/// Aggregated stats of a user across all games of a [`Match`], see [`Match::user_stats`]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct MatchUserStats {
+    pub total_score: u64,
+    pub average_accuracy: f32,
+    pub games_played: u32,
+    pub wins: u32,
+}
+
 /// Basic enum to describe the scoring type of a [`Match`]
 /// i.e. the winning condition
 #[derive(Debug, Clone, Hash, Copy, Eq, PartialEq)]
@@ -241,6 +439,19 @@ impl From<u8> for ScoringType {
     }
 }
 
+impl Display for ScoringType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let scoring_type = match self {
+            Self::Score => "Score",
+            Self::Accuracy => "Accuracy",
+            Self::Combo => "Combo",
+            Self::ScoreV2 => "Score V2",
+        };
+
+        f.write_str(scoring_type)
+    }
+}
+
 /// Basic enum to describe the team type of a [`Match`]
 #[derive(Debug, Clone, Hash, Copy, Eq, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(Serialize_repr))]
@@ -264,6 +475,19 @@ impl From<u8> for TeamType {
     }
 }
 
+impl Display for TeamType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let team_type = match self {
+            Self::HeadToHead => "Head To Head",
+            Self::TagCoop => "Tag Co-op",
+            Self::TeamVS => "Team VS",
+            Self::TagTeamVS => "Tag Team VS",
+        };
+
+        f.write_str(team_type)
+    }
+}
+
 /// Basic enum to declare a team of a [`Match`]
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(Serialize_repr))]
@@ -284,3 +508,352 @@ impl From<u8> for Team {
         }
     }
 }
+
+impl Display for Team {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let team = match self {
+            Self::None => "None",
+            Self::Blue => "Blue",
+            Self::Red => "Red",
+        };
+
+        f.write_str(team)
+    }
+}
+
+impl From<&GameScore> for HitCounts {
+    fn from(score: &GameScore) -> Self {
+        Self {
+            count300: score.count300,
+            count100: score.count100,
+            count50: score.count50,
+            count_miss: score.count_miss,
+            count_geki: score.count_geki,
+            count_katu: score.count_katu,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scoring_type_display() {
+        assert_eq!(ScoringType::Score.to_string(), "Score");
+        assert_eq!(ScoringType::Accuracy.to_string(), "Accuracy");
+        assert_eq!(ScoringType::Combo.to_string(), "Combo");
+        assert_eq!(ScoringType::ScoreV2.to_string(), "Score V2");
+    }
+
+    #[test]
+    fn team_type_display() {
+        assert_eq!(TeamType::HeadToHead.to_string(), "Head To Head");
+        assert_eq!(TeamType::TagCoop.to_string(), "Tag Co-op");
+        assert_eq!(TeamType::TeamVS.to_string(), "Team VS");
+        assert_eq!(TeamType::TagTeamVS.to_string(), "Tag Team VS");
+    }
+
+    #[test]
+    fn team_display() {
+        assert_eq!(Team::None.to_string(), "None");
+        assert_eq!(Team::Blue.to_string(), "Blue");
+        assert_eq!(Team::Red.to_string(), "Red");
+    }
+
+    #[test]
+    fn match_source_endpoint() {
+        assert_eq!(Match::source_endpoint(), "get_match");
+    }
+
+    fn game(game_id: u32, start_time: OffsetDateTime) -> MatchGame {
+        MatchGame {
+            game_id,
+            start_time,
+            end_time: None,
+            beatmap_id: 0,
+            mode: GameMode::Osu,
+            scoring_type: ScoringType::ScoreV2,
+            team_type: TeamType::HeadToHead,
+            mods: None,
+            scores: Vec::new(),
+        }
+    }
+
+    fn date(timestamp: i64) -> OffsetDateTime {
+        OffsetDateTime::from_unix_timestamp(timestamp).unwrap()
+    }
+
+    #[test]
+    fn match_merge() {
+        let first = Match {
+            match_id: 1,
+            name: "OWC: (Germany) vs (Japan)".to_owned(),
+            start_time: date(1000),
+            end_time: Some(date(2000)),
+            games: vec![game(10, date(1100)), game(11, date(1500))],
+        };
+
+        let second = Match {
+            match_id: 2,
+            name: "OWC: (Germany) vs (Japan)".to_owned(),
+            start_time: date(2100),
+            end_time: Some(date(3000)),
+            games: vec![game(11, date(1500)), game(20, date(2200))],
+        };
+
+        let merged = first.merge(&second);
+        assert_eq!(merged.match_id, 1);
+        assert_eq!(merged.start_time, date(1000));
+        assert_eq!(merged.end_time, Some(date(3000)));
+        let game_ids: Vec<_> = merged.games.iter().map(|game| game.game_id).collect();
+        assert_eq!(game_ids, vec![10, 11, 20]);
+    }
+
+    #[test]
+    fn match_games_chronological() {
+        let osu_match = Match {
+            match_id: 1,
+            name: "OWC: (Germany) vs (Japan)".to_owned(),
+            start_time: date(1000),
+            end_time: None,
+            games: vec![
+                game(12, date(1900)),
+                game(10, date(1100)),
+                game(13, date(1900)),
+                game(11, date(1500)),
+            ],
+        };
+
+        let game_ids: Vec<_> = osu_match
+            .games_chronological()
+            .into_iter()
+            .map(|game| game.game_id)
+            .collect();
+        assert_eq!(game_ids, vec![10, 11, 12, 13]);
+    }
+
+    #[test]
+    fn match_game_id_gaps() {
+        let mut osu_match = Match {
+            match_id: 1,
+            name: "OWC: (Germany) vs (Japan)".to_owned(),
+            start_time: date(1000),
+            end_time: None,
+            games: Vec::new(),
+        };
+        assert!(osu_match.game_id_gaps().is_empty());
+
+        osu_match.games = vec![
+            game(10, date(1100)),
+            game(14, date(1900)),
+            game(11, date(1500)),
+            game(13, date(1700)),
+        ];
+        assert_eq!(osu_match.game_id_gaps(), vec![12]);
+    }
+
+    fn game_score(user_id: u32, score: u32, count300: u32, count100: u32) -> GameScore {
+        GameScore {
+            slot: 0,
+            team: Team::None,
+            user_id,
+            score,
+            max_combo: 0,
+            count50: 0,
+            count100,
+            count300,
+            count_miss: 0,
+            count_geki: 0,
+            count_katu: 0,
+            perfect: false,
+            pass: true,
+            enabled_mods: None,
+        }
+    }
+
+    #[test]
+    fn game_score_effective_mods() {
+        let mut game = game(1, date(1000));
+        game.mods = Some(GameMods::HardRock);
+
+        let score = GameScore {
+            enabled_mods: Some(GameMods::Hidden),
+            ..game_score(2, 1_000_000, 100, 0)
+        };
+        let mods = score.effective_mods(&game);
+        assert_eq!(mods, GameMods::Hidden | GameMods::HardRock);
+        assert_eq!(mods.to_string(), "HDHR");
+
+        let nomod = game_score(3, 1_000_000, 100, 0);
+        assert_eq!(nomod.effective_mods(&game), GameMods::HardRock);
+    }
+
+    #[test]
+    fn match_user_stats() {
+        let mut first = game(1, date(1000));
+        first.scores = vec![
+            game_score(2, 1_000_000, 100, 0),
+            game_score(3, 800_000, 90, 10),
+        ];
+
+        let mut second = game(2, date(1500));
+        second.scores = vec![
+            game_score(2, 700_000, 80, 20),
+            game_score(3, 900_000, 100, 0),
+        ];
+
+        let osu_match = Match {
+            match_id: 1,
+            name: "OWC: (Germany) vs (Japan)".to_owned(),
+            start_time: date(1000),
+            end_time: None,
+            games: vec![first, second],
+        };
+
+        let stats = osu_match.user_stats();
+        assert_eq!(stats.len(), 2);
+
+        let user = stats[&2];
+        assert_eq!(user.total_score, 1_700_000);
+        assert_eq!(user.games_played, 2);
+        assert_eq!(user.wins, 1);
+        assert!((user.average_accuracy - 93.335).abs() < 0.01);
+
+        let mut team_game = game(3, date(2000));
+        team_game.team_type = TeamType::TeamVS;
+        team_game.scores = vec![
+            GameScore {
+                team: Team::Blue,
+                ..game_score(2, 500_000, 100, 0)
+            },
+            GameScore {
+                team: Team::Red,
+                ..game_score(3, 400_000, 100, 0)
+            },
+            GameScore {
+                team: Team::Red,
+                ..game_score(4, 300_000, 100, 0)
+            },
+        ];
+        assert_eq!(team_game.winners(), vec![3, 4]);
+    }
+
+    #[test]
+    fn match_game_winners_scoring_type() {
+        let mut game = game(1, date(1000));
+        game.scores = vec![
+            GameScore {
+                max_combo: 500,
+                ..game_score(2, 1_000_000, 90, 10)
+            },
+            GameScore {
+                max_combo: 300,
+                ..game_score(3, 800_000, 100, 0)
+            },
+            GameScore {
+                max_combo: 500,
+                ..game_score(4, 700_000, 80, 20)
+            },
+        ];
+        assert_eq!(game.winners(), vec![2]);
+
+        game.scoring_type = ScoringType::Accuracy;
+        assert_eq!(game.winners(), vec![3]);
+
+        game.scoring_type = ScoringType::Combo;
+        assert_eq!(game.winners(), vec![2, 4]);
+
+        game.team_type = TeamType::TeamVS;
+        for score in game.scores.iter_mut() {
+            score.team = Team::Blue;
+        }
+        assert!(game.winners().is_empty());
+
+        game.scoring_type = ScoringType::Score;
+        assert_eq!(game.winners(), vec![2, 3, 4]);
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn match_serde_round_trip() {
+        let json = r#"{
+            "match": {
+                "match_id": "58494587",
+                "name": "OWC: (Germany) vs (Japan)",
+                "start_time": "2019-12-14 08:01:29",
+                "end_time": "2019-12-14 09:48:57"
+            },
+            "games": [
+                {
+                    "game_id": "307047418",
+                    "start_time": "2019-12-14 08:13:45",
+                    "end_time": "2019-12-14 08:17:24",
+                    "beatmap_id": "2271405",
+                    "play_mode": "0",
+                    "match_type": "0",
+                    "scoring_type": "3",
+                    "team_type": "2",
+                    "mods": "1",
+                    "scores": [
+                        {
+                            "slot": "0",
+                            "team": "1",
+                            "user_id": "2",
+                            "score": "876543",
+                            "maxcombo": "1234",
+                            "rank": "0",
+                            "count50": "1",
+                            "count100": "12",
+                            "count300": "980",
+                            "countmiss": "2",
+                            "countgeki": "150",
+                            "countkatu": "8",
+                            "perfect": "0",
+                            "pass": "1",
+                            "enabled_mods": "24"
+                        },
+                        {
+                            "slot": "1",
+                            "team": "2",
+                            "user_id": "3",
+                            "score": "765432",
+                            "maxcombo": "987",
+                            "rank": "0",
+                            "count50": "0",
+                            "count100": "20",
+                            "count300": "975",
+                            "countmiss": "0",
+                            "countgeki": "140",
+                            "countkatu": "15",
+                            "perfect": "1",
+                            "pass": "1",
+                            "enabled_mods": null
+                        }
+                    ]
+                },
+                {
+                    "game_id": "307046954",
+                    "start_time": "2019-12-14 08:05:12",
+                    "end_time": null,
+                    "beatmap_id": "905576",
+                    "play_mode": "0",
+                    "match_type": "0",
+                    "scoring_type": "3",
+                    "team_type": "2",
+                    "mods": "0",
+                    "scores": []
+                }
+            ]
+        }"#;
+
+        let osu_match: Match = serde_json::from_str(json).unwrap();
+        let game_ids: Vec<_> = osu_match.games.iter().map(|game| game.game_id).collect();
+        assert_eq!(game_ids, vec![307047418, 307046954]);
+
+        let serialized = serde_json::to_string(&osu_match).unwrap();
+        let deserialized: Match = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(osu_match, deserialized);
+        assert_eq!(osu_match.games[0].scores, deserialized.games[0].scores);
+    }
+}
@@ -1,15 +1,25 @@
 mod beatmap;
+#[cfg(feature = "serialize")]
+mod compact_score;
+#[cfg(feature = "serialize")]
+mod enriched_score;
 mod grade;
 mod r#match;
 mod mode;
 mod mods;
+mod replay;
 mod score;
 mod user;
 
 pub use beatmap::{ApprovalStatus, Beatmap, Genre, Language};
+#[cfg(feature = "serialize")]
+pub use compact_score::CompactScore;
+#[cfg(feature = "serialize")]
+pub use enriched_score::EnrichedScore;
 pub use grade::Grade;
 pub use mode::GameMode;
-pub use mods::GameMods;
-pub use r#match::{GameScore, Match, MatchGame, ScoringType, Team, TeamType};
+pub use mods::{DifficultyMultipliers, GameMods};
+pub use r#match::{GameScore, Match, MatchGame, MatchUserStats, ScoringType, Team, TeamType};
+pub use replay::Replay;
 pub use score::Score;
-pub use user::{Event, User};
+pub use user::{Event, RankSnapshot, User, UserBundle};
@@ -1,4 +1,9 @@
-use std::fmt::{Display, Formatter, Result as FmtResult};
+use crate::OsuError;
+
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    str::FromStr,
+};
 
 #[cfg(feature = "serialize")]
 use serde_repr::Serialize_repr;
@@ -26,7 +31,7 @@ impl Display for GameMode {
         let mode = match self {
             GameMode::Osu => "osu",
             GameMode::Taiko => "taiko",
-            GameMode::Catch => "fruits",
+            GameMode::Catch => "catch",
             GameMode::Mania => "mania",
         };
 
@@ -34,6 +39,39 @@ impl Display for GameMode {
     }
 }
 
+impl FromStr for GameMode {
+    type Err = OsuError;
+
+    /// Parse the mode from its id, its short tag e.g. `mna`, or its name e.g. `mania`,
+    /// ignoring case and an `osu!` prefix of tags and names.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.to_lowercase();
+
+        let mode = match lower.as_str() {
+            "0" => Self::Osu,
+            "1" => Self::Taiko,
+            "2" => Self::Catch,
+            "3" => Self::Mania,
+            _ => {
+                let name = lower
+                    .strip_prefix("osu!")
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or(&lower);
+
+                match name {
+                    "osu" | "osu!" | "std" | "standard" => Self::Osu,
+                    "taiko" | "tko" => Self::Taiko,
+                    "catch" | "ctb" | "fruits" => Self::Catch,
+                    "mania" | "mna" => Self::Mania,
+                    _ => return Err(OsuError::ModeParsing(s.to_owned())),
+                }
+            }
+        };
+
+        Ok(mode)
+    }
+}
+
 impl From<u8> for GameMode {
     #[inline]
     fn from(m: u8) -> Self {
@@ -45,3 +83,57 @@ impl From<u8> for GameMode {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mode_from_str() {
+        let spellings = [
+            (GameMode::Osu, ["0", "std", "osu", "osu!"]),
+            (GameMode::Taiko, ["1", "tko", "taiko", "osu!taiko"]),
+            (GameMode::Catch, ["2", "ctb", "catch", "fruits"]),
+            (GameMode::Mania, ["3", "mna", "mania", "osu!mania"]),
+        ];
+
+        for (mode, spellings) in spellings {
+            for s in spellings {
+                assert_eq!(s.parse::<GameMode>().unwrap(), mode, "{}", s);
+                assert_eq!(s.to_uppercase().parse::<GameMode>().unwrap(), mode, "{}", s);
+            }
+
+            assert_eq!(mode.to_string().parse::<GameMode>().unwrap(), mode);
+        }
+
+        assert!(matches!(
+            "4".parse::<GameMode>(),
+            Err(OsuError::ModeParsing(s)) if s == "4"
+        ));
+        assert!("osu!drums".parse::<GameMode>().is_err());
+        assert!("osu!2".parse::<GameMode>().is_err());
+        assert!("".parse::<GameMode>().is_err());
+    }
+
+    #[test]
+    fn mode_display() {
+        assert_eq!(GameMode::Osu.to_string(), "osu");
+        assert_eq!(GameMode::Taiko.to_string(), "taiko");
+        assert_eq!(GameMode::Catch.to_string(), "catch");
+        assert_eq!(GameMode::Mania.to_string(), "mania");
+    }
+
+    #[test]
+    fn mode_deserialize() {
+        for json in ["3", r#""3""#, r#""mania""#, r#""osu!mania""#] {
+            let mode: GameMode = serde_json::from_str(json).unwrap();
+            assert_eq!(mode, GameMode::Mania, "{}", json);
+        }
+
+        let mode: GameMode = serde_json::from_str(r#""std""#).unwrap();
+        assert_eq!(mode, GameMode::Osu);
+
+        assert!(serde_json::from_str::<GameMode>("4").is_err());
+        assert!(serde_json::from_str::<GameMode>(r#""4""#).is_err());
+    }
+}
@@ -1,6 +1,6 @@
 #![allow(non_upper_case_globals)]
 
-use crate::{error::ModError, model::GameMode, OsuError};
+use crate::{error::ModError, model::GameMode, OsuError, OsuResult};
 
 use std::{
     convert::TryFrom,
@@ -122,6 +122,33 @@ impl GameMods {
         }
     }
 
+    /// The amount of keys specified by the contained key mod, if any.
+    ///
+    /// # Example
+    /// ```
+    /// use rosu::model::GameMods;
+    ///
+    /// let mods = GameMods::Hidden | GameMods::Key7;
+    /// assert_eq!(mods.mania_key_count(), Some(7));
+    /// assert_eq!(GameMods::KeyCoop.mania_key_count(), None);
+    /// ```
+    pub fn mania_key_count(self) -> Option<u8> {
+        let count = match self.has_key_mod()? {
+            GameMods::Key1 => 1,
+            GameMods::Key2 => 2,
+            GameMods::Key3 => 3,
+            GameMods::Key4 => 4,
+            GameMods::Key5 => 5,
+            GameMods::Key6 => 6,
+            GameMods::Key7 => 7,
+            GameMods::Key8 => 8,
+            GameMods::Key9 => 9,
+            _ => return None,
+        };
+
+        Some(count)
+    }
+
     /// Calculate the multiplier of the mods which will
     /// influence a [`Score`]'s playscore
     ///
@@ -228,6 +255,61 @@ impl GameMods {
         }
     }
 
+    /// Returns the multipliers of the mods for a beatmap's CS, AR, OD, and HP.
+    ///
+    /// `HardRock` multiplies CS by 1.3 and all other values by 1.4,
+    /// `Easy` multiplies all values by 0.5.
+    /// Note that the adjusted values should be capped at 10.
+    ///
+    /// # Example
+    /// ```
+    /// use rosu::model::GameMods;
+    ///
+    /// let multipliers = GameMods::HardRock.difficulty_multipliers();
+    /// assert_eq!(multipliers.ar, 1.4);
+    /// assert_eq!(GameMods::Hidden.difficulty_multipliers().ar, 1.0);
+    /// ```
+    pub fn difficulty_multipliers(self) -> DifficultyMultipliers {
+        let mut multipliers = DifficultyMultipliers::default();
+
+        if self.contains(GameMods::HardRock) {
+            multipliers.cs *= 1.3;
+            multipliers.ar *= 1.4;
+            multipliers.od *= 1.4;
+            multipliers.hp *= 1.4;
+        }
+
+        if self.contains(GameMods::Easy) {
+            multipliers.cs *= 0.5;
+            multipliers.ar *= 0.5;
+            multipliers.od *= 0.5;
+            multipliers.hp *= 0.5;
+        }
+
+        multipliers
+    }
+
+    /// Returns the speed multiplier of the mods i.e. `1.5` for `DoubleTime` and `NightCore`,
+    /// `0.75` for `HalfTime`, and `1.0` otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// use rosu::model::GameMods;
+    ///
+    /// assert_eq!(GameMods::NightCore.clock_rate(), 1.5);
+    /// assert_eq!(GameMods::HalfTime.clock_rate(), 0.75);
+    /// assert_eq!(GameMods::Hidden.clock_rate(), 1.0);
+    /// ```
+    pub fn clock_rate(self) -> f32 {
+        if self.intersects(GameMods::DoubleTime | GameMods::NightCore) {
+            1.5
+        } else if self.contains(GameMods::HalfTime) {
+            0.75
+        } else {
+            1.0
+        }
+    }
+
     /// Returns an iterator. Alias of `into_iter`.
     ///
     /// # Example
@@ -247,6 +329,241 @@ impl GameMods {
         self.into_iter()
     }
 
+    /// Returns the value that the osu!api expects for the given mods.
+    ///
+    /// # Example
+    /// ```
+    /// use rosu::model::GameMods;
+    ///
+    /// let hdhr = GameMods::Hidden | GameMods::HardRock;
+    /// assert_eq!(hdhr.to_api_value(), 8 + 16);
+    /// ```
+    #[inline]
+    pub fn to_api_value(self) -> u32 {
+        self.bits
+    }
+
+    /// Parse mods from a value as provided by the osu!api.
+    ///
+    /// # Example
+    /// ```
+    /// use rosu::model::GameMods;
+    ///
+    /// let hdhr = GameMods::from_api_value(8 + 16).unwrap();
+    /// assert_eq!(hdhr, GameMods::Hidden | GameMods::HardRock);
+    /// ```
+    #[inline]
+    pub fn from_api_value(value: u32) -> OsuResult<Self> {
+        GameMods::from_bits(value).ok_or(OsuError::ModParsing(ModError::U32(value)))
+    }
+
+    /// Parse mods from a value that may contain bits unknown to rosu,
+    /// e.g. mods of scores imported from lazer.
+    ///
+    /// Returns the recognized mods and the leftover unknown bits.
+    ///
+    /// # Example
+    /// ```
+    /// use rosu::model::GameMods;
+    ///
+    /// let (mods, unknown) = GameMods::from_bits_partial(8 + 16 + (1 << 31));
+    /// assert_eq!(mods, GameMods::Hidden | GameMods::HardRock);
+    /// assert_eq!(unknown, 1 << 31);
+    /// ```
+    #[inline]
+    pub fn from_bits_partial(value: u32) -> (Self, u32) {
+        (
+            GameMods::from_bits_truncate(value),
+            value & !GameMods::all().bits,
+        )
+    }
+
+    /// Returns the bits of the mods where composite mods always include the
+    /// bits of the mods they imply i.e. `NightCore` includes `DoubleTime`
+    /// and `Perfect` includes `SuddenDeath`.
+    ///
+    /// Values from the api occasionally only contain the bit of the composite mod,
+    /// normalizing them makes comparisons reliable.
+    /// `NightCore` and `DoubleTime` still differ by the `NightCore` bit.
+    ///
+    /// # Example
+    /// ```
+    /// use rosu::model::GameMods;
+    ///
+    /// let nc = GameMods::from_bits(512).unwrap();
+    /// assert_eq!(nc.normalized_bits(), GameMods::NightCore.bits());
+    /// assert_eq!(nc.normalized_bits(), 512 + 64);
+    /// ```
+    pub fn normalized_bits(self) -> u32 {
+        let mut bits = self.bits;
+
+        if self.has_nightcore() {
+            bits |= GameMods::DoubleTime.bits;
+        }
+
+        if self.has_perfect() {
+            bits |= GameMods::SuddenDeath.bits;
+        }
+
+        bits
+    }
+
+    /// Check whether the mods contain `NightCore`.
+    ///
+    /// Only checks the `NightCore` bit so it does not matter whether
+    /// the implied `DoubleTime` bit is set as well.
+    ///
+    /// # Example
+    /// ```
+    /// use rosu::model::GameMods;
+    ///
+    /// assert!(GameMods::NightCore.has_nightcore());
+    /// assert!(!GameMods::DoubleTime.has_nightcore());
+    /// ```
+    #[inline]
+    pub fn has_nightcore(self) -> bool {
+        self.intersects(GameMods::NightCore - GameMods::DoubleTime)
+    }
+
+    /// Check whether the mods contain `DoubleTime` but not `NightCore`.
+    ///
+    /// # Example
+    /// ```
+    /// use rosu::model::GameMods;
+    ///
+    /// assert!(GameMods::DoubleTime.has_doubletime_only());
+    /// assert!(!GameMods::NightCore.has_doubletime_only());
+    /// ```
+    #[inline]
+    pub fn has_doubletime_only(self) -> bool {
+        self.contains(GameMods::DoubleTime) && !self.has_nightcore()
+    }
+
+    /// Check whether the mods contain `Perfect`.
+    ///
+    /// Only checks the `Perfect` bit so it does not matter whether
+    /// the implied `SuddenDeath` bit is set as well.
+    ///
+    /// # Example
+    /// ```
+    /// use rosu::model::GameMods;
+    ///
+    /// assert!(GameMods::Perfect.has_perfect());
+    /// assert!(!GameMods::SuddenDeath.has_perfect());
+    /// ```
+    #[inline]
+    pub fn has_perfect(self) -> bool {
+        self.intersects(GameMods::Perfect - GameMods::SuddenDeath)
+    }
+
+    /// Check whether the mods contain `SuddenDeath` but not `Perfect`.
+    ///
+    /// # Example
+    /// ```
+    /// use rosu::model::GameMods;
+    ///
+    /// assert!(GameMods::SuddenDeath.has_suddendeath_only());
+    /// assert!(!GameMods::Perfect.has_suddendeath_only());
+    /// ```
+    #[inline]
+    pub fn has_suddendeath_only(self) -> bool {
+        self.contains(GameMods::SuddenDeath) && !self.has_perfect()
+    }
+
+    /// Check whether both mods are equal after normalizing them, see [`GameMods::normalized_bits`].
+    ///
+    /// # Example
+    /// ```
+    /// use rosu::model::GameMods;
+    ///
+    /// let pf = GameMods::from_bits(16_384).unwrap();
+    /// assert_ne!(pf, GameMods::Perfect);
+    /// assert!(pf.eq_effective(GameMods::Perfect));
+    /// ```
+    #[inline]
+    pub fn eq_effective(self, other: GameMods) -> bool {
+        self.normalized_bits() == other.normalized_bits()
+    }
+
+    /// Remove the mods that do not affect the difficulty calculation
+    /// i.e. `Autoplay`, `Cinema`, `ScoreV2`, `Random`, and `Target`.
+    ///
+    /// # Example
+    /// ```
+    /// use rosu::model::GameMods;
+    ///
+    /// let mods = GameMods::Hidden | GameMods::DoubleTime | GameMods::ScoreV2;
+    /// assert_eq!(mods.gameplay_relevant(), GameMods::Hidden | GameMods::DoubleTime);
+    /// ```
+    #[inline]
+    pub fn gameplay_relevant(self) -> GameMods {
+        self - (GameMods::Autoplay
+            | GameMods::Cinema
+            | GameMods::ScoreV2
+            | GameMods::Random
+            | GameMods::Target)
+    }
+
+    /// The acronym of each contained mod, e.g. for interop with lazer tooling.
+    ///
+    /// Mods without acronym such as `Autoplay` are skipped
+    /// and `NoMod` results in an empty vec.
+    ///
+    /// # Example
+    /// ```
+    /// use rosu::model::GameMods;
+    ///
+    /// let mods = GameMods::Hidden | GameMods::DoubleTime;
+    /// assert_eq!(mods.acronyms(), vec!["HD", "DT"]);
+    /// assert!(GameMods::NoMod.acronyms().is_empty());
+    /// ```
+    pub fn acronyms(self) -> Vec<&'static str> {
+        self.into_iter()
+            .filter(|&m| m != GameMods::NoMod)
+            .map(GameMods::abbreviation)
+            .filter(|abbrev| !abbrev.is_empty())
+            .collect()
+    }
+
+    /// The abbreviation of a single mod, empty for mods without abbreviation.
+    fn abbreviation(self) -> &'static str {
+        match self {
+            GameMods::NoMod => "NM",
+            GameMods::NoFail => "NF",
+            GameMods::Easy => "EZ",
+            GameMods::TouchDevice => "TD",
+            GameMods::Hidden => "HD",
+            GameMods::HardRock => "HR",
+            GameMods::SuddenDeath => "SD",
+            GameMods::DoubleTime => "DT",
+            GameMods::Relax => "RX",
+            GameMods::HalfTime => "HT",
+            GameMods::NightCore => "NC",
+            GameMods::Flashlight => "FL",
+            GameMods::SpunOut => "SO",
+            GameMods::Autopilot => "AP",
+            GameMods::Perfect => "PF",
+            GameMods::FadeIn => "FI",
+            GameMods::Random => "RD",
+            GameMods::Target => "TP",
+            GameMods::ScoreV2 => "V2",
+            GameMods::Mirror => "MR",
+            GameMods::Key1 => "1K",
+            GameMods::Key2 => "2K",
+            GameMods::Key3 => "3K",
+            GameMods::Key4 => "4K",
+            GameMods::Key5 => "5K",
+            GameMods::Key6 => "6K",
+            GameMods::Key7 => "7K",
+            GameMods::Key8 => "8K",
+            GameMods::Key9 => "9K",
+            GameMods::Autoplay => "",
+            GameMods::Cinema => "",
+            GameMods::KeyCoop => "",
+            _ => unreachable!(),
+        }
+    }
+
     /// Returns the amount of contained mods.
     ///
     /// # Example
@@ -266,46 +583,43 @@ impl GameMods {
     }
 }
 
+/// Multipliers for a beatmap's difficulty values, see [`GameMods::difficulty_multipliers`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DifficultyMultipliers {
+    pub cs: f32,
+    pub ar: f32,
+    pub od: f32,
+    pub hp: f32,
+}
+
+impl Default for DifficultyMultipliers {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            cs: 1.0,
+            ar: 1.0,
+            od: 1.0,
+            hp: 1.0,
+        }
+    }
+}
+
 impl Display for GameMods {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut empty = true;
+
         for m in self.into_iter() {
-            let abbrev = match m {
-                GameMods::NoMod => "NM",
-                GameMods::NoFail => "NF",
-                GameMods::Easy => "EZ",
-                GameMods::TouchDevice => "TD",
-                GameMods::Hidden => "HD",
-                GameMods::HardRock => "HR",
-                GameMods::SuddenDeath => "SD",
-                GameMods::DoubleTime => "DT",
-                GameMods::Relax => "RX",
-                GameMods::HalfTime => "HT",
-                GameMods::NightCore => "NC",
-                GameMods::Flashlight => "FL",
-                GameMods::SpunOut => "SO",
-                GameMods::Autopilot => "AP",
-                GameMods::Perfect => "PF",
-                GameMods::FadeIn => "FI",
-                GameMods::Random => "RD",
-                GameMods::Target => "TP",
-                GameMods::ScoreV2 => "V2",
-                GameMods::Mirror => "MR",
-                GameMods::Key1 => "1K",
-                GameMods::Key2 => "2K",
-                GameMods::Key3 => "3K",
-                GameMods::Key4 => "4K",
-                GameMods::Key5 => "5K",
-                GameMods::Key6 => "6K",
-                GameMods::Key7 => "7K",
-                GameMods::Key8 => "8K",
-                GameMods::Key9 => "9K",
-                GameMods::Autoplay => "",
-                GameMods::Cinema => "",
-                GameMods::KeyCoop => "",
-                _ => unreachable!(),
-            };
+            let abbrev = m.abbreviation();
+
+            if !abbrev.is_empty() {
+                f.write_str(abbrev)?;
+                empty = false;
+            }
+        }
 
-            f.write_str(abbrev)?;
+        // Mods without abbreviation e.g. `Autoplay` should not produce an empty string
+        if empty {
+            f.write_str("NM")?;
         }
 
         Ok(())
@@ -324,7 +638,7 @@ impl TryFrom<u32> for GameMods {
 
     #[inline]
     fn try_from(m: u32) -> Result<Self, Self::Error> {
-        GameMods::from_bits(m).ok_or(OsuError::ModParsing(ModError::U32(m)))
+        GameMods::from_api_value(m)
     }
 }
 
@@ -333,7 +647,7 @@ impl FromStr for GameMods {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut res = GameMods::default();
-        let upper = s.to_uppercase();
+        let upper = s.strip_prefix('+').unwrap_or(s).to_uppercase();
 
         for m in util::cut(&upper, 2) {
             let m = match m {
@@ -483,6 +797,35 @@ mod tests {
         assert!(GameMods::from_str("HHDR").is_err());
     }
 
+    #[test]
+    fn test_mods_from_str_composite_bits() {
+        assert_eq!(GameMods::from_str("NC").unwrap().bits(), 512 | 64);
+        assert_eq!(GameMods::from_str("PF").unwrap().bits(), 16_384 | 32);
+        assert_eq!(
+            GameMods::from_str("HDNCPF").unwrap().bits(),
+            8 | 512 | 64 | 16_384 | 32
+        );
+    }
+
+    #[test]
+    fn test_mods_acronyms() {
+        let mods = GameMods::Hidden | GameMods::HardRock | GameMods::DoubleTime;
+        assert_eq!(mods.acronyms(), vec!["HD", "HR", "DT"]);
+        assert_eq!(
+            (GameMods::Autoplay | GameMods::Hidden).acronyms(),
+            vec!["HD"]
+        );
+    }
+
+    #[test]
+    fn test_mods_try_from_str_plus_prefix() {
+        let mods = GameMods::Hidden | GameMods::HardRock;
+        assert_eq!(GameMods::from_str("+HDHR").unwrap(), mods);
+        assert_eq!(GameMods::from_str("+nomod").unwrap(), GameMods::NoMod);
+        assert!(GameMods::from_str("++HDHR").is_err());
+        assert!(GameMods::from_str("HD+HR").is_err());
+    }
+
     #[test]
     fn test_mods_iter() {
         let mut iter = GameMods::default().iter();
@@ -493,4 +836,152 @@ mod tests {
         assert_eq!(iter.next().unwrap(), GameMods::HardRock);
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn test_mods_display() {
+        assert_eq!(GameMods::NoMod.to_string(), "NM");
+        assert_eq!(GameMods::Autoplay.to_string(), "NM");
+        assert_eq!((GameMods::Autoplay | GameMods::Hidden).to_string(), "HD");
+    }
+
+    #[test]
+    fn test_mods_api_value() {
+        let mods = GameMods::Hidden | GameMods::NightCore | GameMods::Key4;
+        let value = mods.to_api_value();
+        assert_eq!(value, 8 + 512 + 64 + 32_768);
+        assert_eq!(GameMods::from_api_value(value).unwrap(), mods);
+        assert!(GameMods::from_api_value(1 << 31).is_err());
+    }
+
+    #[test]
+    fn test_mods_composite_accessors() {
+        let nc = GameMods::NightCore | GameMods::Hidden;
+        assert!(nc.has_nightcore());
+        assert!(!nc.has_doubletime_only());
+        assert!(nc.contains(GameMods::DoubleTime));
+
+        let dt = GameMods::DoubleTime | GameMods::Hidden;
+        assert!(!dt.has_nightcore());
+        assert!(dt.has_doubletime_only());
+
+        let pf = GameMods::Perfect;
+        assert!(pf.has_perfect());
+        assert!(!pf.has_suddendeath_only());
+        assert!(GameMods::SuddenDeath.has_suddendeath_only());
+        assert!(!GameMods::NoMod.has_suddendeath_only());
+    }
+
+    #[test]
+    fn test_mods_normalized_bits() {
+        let nc = GameMods::NightCore;
+        let dt = GameMods::DoubleTime;
+        assert!(!nc.eq_effective(dt));
+        assert_eq!(nc.normalized_bits() ^ dt.normalized_bits(), 512);
+
+        let nc_only = GameMods::from_bits(512 + 8).unwrap();
+        assert_ne!(nc_only, GameMods::NightCore | GameMods::Hidden);
+        assert!(nc_only.eq_effective(GameMods::NightCore | GameMods::Hidden));
+
+        let pf_only = GameMods::from_bits(16_384).unwrap();
+        assert_eq!(pf_only.normalized_bits(), 16_384 + 32);
+        assert!(pf_only.eq_effective(GameMods::Perfect));
+        assert!(!pf_only.eq_effective(GameMods::SuddenDeath));
+    }
+
+    #[test]
+    fn test_mods_difficulty_multipliers() {
+        let hr = GameMods::HardRock.difficulty_multipliers();
+        assert!((hr.cs - 1.3).abs() < f32::EPSILON);
+        assert!((hr.ar - 1.4).abs() < f32::EPSILON);
+        assert!((hr.od - 1.4).abs() < f32::EPSILON);
+        assert!((hr.hp - 1.4).abs() < f32::EPSILON);
+
+        let ezhd = (GameMods::Easy | GameMods::Hidden).difficulty_multipliers();
+        assert!((ezhd.cs - 0.5).abs() < f32::EPSILON);
+        assert!((ezhd.ar - 0.5).abs() < f32::EPSILON);
+        assert!((ezhd.od - 0.5).abs() < f32::EPSILON);
+        assert!((ezhd.hp - 0.5).abs() < f32::EPSILON);
+
+        assert_eq!(
+            GameMods::DoubleTime.difficulty_multipliers(),
+            DifficultyMultipliers::default()
+        );
+    }
+
+    #[test]
+    fn test_mods_gameplay_relevant() {
+        let mods = GameMods::Hidden | GameMods::DoubleTime | GameMods::ScoreV2;
+        assert_eq!(
+            mods.gameplay_relevant(),
+            GameMods::Hidden | GameMods::DoubleTime
+        );
+
+        let mods = GameMods::NightCore | GameMods::Autoplay | GameMods::Cinema | GameMods::Target;
+        assert_eq!(mods.gameplay_relevant(), GameMods::NightCore);
+
+        assert_eq!(GameMods::Random.gameplay_relevant(), GameMods::NoMod);
+        assert_eq!(GameMods::HardRock.gameplay_relevant(), GameMods::HardRock);
+    }
+
+    #[test]
+    fn test_mods_from_bits_partial() {
+        let value = 8 + 64 + (1 << 31);
+        assert!(GameMods::try_from(value).is_err());
+
+        let (mods, unknown) = GameMods::from_bits_partial(value);
+        assert_eq!(mods, GameMods::Hidden | GameMods::DoubleTime);
+        assert_eq!(unknown, 1 << 31);
+
+        let (mods, unknown) = GameMods::from_bits_partial(mods.bits());
+        assert_eq!(mods, GameMods::Hidden | GameMods::DoubleTime);
+        assert_eq!(unknown, 0);
+    }
+}
+
+#[cfg(test)]
+mod serde_tests {
+    use super::*;
+    use serde::{de::IntoDeserializer, Deserialize};
+
+    fn round_trip(mods: GameMods) -> GameMods {
+        let serialized = serde_json::to_string(&mods).unwrap();
+        assert_eq!(serialized, mods.bits().to_string());
+
+        serde_json::from_str(&serialized).unwrap()
+    }
+
+    #[test]
+    fn test_mods_serde_single() {
+        assert_eq!(round_trip(GameMods::NoMod), GameMods::NoMod);
+
+        for mods in GameMods::all() {
+            assert_eq!(round_trip(mods), mods, "{:?}", mods);
+        }
+    }
+
+    #[test]
+    fn test_mods_serde_composite() {
+        let composites = [
+            GameMods::NightCore | GameMods::Hidden,
+            GameMods::Perfect | GameMods::HardRock,
+            GameMods::Hidden | GameMods::HardRock | GameMods::DoubleTime | GameMods::Flashlight,
+            GameMods::Key4 | GameMods::FadeIn | GameMods::Mirror,
+            GameMods::ScoreV2 | GameMods::NoFail | GameMods::HalfTime,
+            GameMods::all(),
+        ];
+
+        for mods in composites {
+            assert_eq!(round_trip(mods), mods, "{:?}", mods);
+        }
+    }
+
+    #[test]
+    fn test_mods_serde_signed() {
+        let deserializer = IntoDeserializer::<serde::de::value::Error>::into_deserializer(24_i64);
+        let mods = GameMods::deserialize(deserializer).unwrap();
+        assert_eq!(mods, GameMods::Hidden | GameMods::HardRock);
+
+        let deserializer = IntoDeserializer::<serde::de::value::Error>::into_deserializer(-1_i64);
+        assert!(GameMods::deserialize(deserializer).is_err());
+    }
 }
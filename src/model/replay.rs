@@ -0,0 +1,16 @@
+/// Replay data retrieved from the `/api/get_replay` endpoint.
+///
+/// The content is the decoded replay data i.e. the LZMA stream of an `.osr` file,
+/// not the whole file.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Replay {
+    pub content: Vec<u8>,
+}
+
+impl Replay {
+    /// The osu!api endpoint that replays are retrieved from.
+    #[inline]
+    pub fn source_endpoint() -> &'static str {
+        "get_replay"
+    }
+}
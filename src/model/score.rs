@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{cmp::Ordering, time::Duration};
 
 use crate::{
     model::{GameMode, GameMods, Grade},
@@ -15,8 +15,12 @@ use time::OffsetDateTime;
 
 /// Score struct retrieved from `/api/get_scores`, `/api/get_user_best`,
 /// and `/api/get_user_recent` endpoints.
+///
+/// Marked as `#[non_exhaustive]` so that fields can be added without a breaking change.
+/// Create scores through [`Score::new`] and set further fields afterwards.
 #[derive(Debug, Clone, Deserialize)]
 #[cfg_attr(feature = "serialize", derive(Serialize))]
+#[non_exhaustive]
 pub struct Score {
     #[serde(
         default,
@@ -93,6 +97,7 @@ pub struct Score {
     )]
     pub perfect: bool,
     pub enabled_mods: GameMods,
+    /// The date the score was set, in UTC
     #[serde(with = "serde_date")]
     pub date: OffsetDateTime,
     #[serde(alias = "rank")]
@@ -109,6 +114,9 @@ pub struct Score {
         skip_serializing_if = "Option::is_none"
     )]
     pub replay_available: Option<bool>,
+    /// The mode the score was requested in, `None` if it was not specified.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<GameMode>,
 }
 
 impl Default for Score {
@@ -132,10 +140,14 @@ impl Default for Score {
             grade: Grade::F,
             pp: None,
             replay_available: None,
+            mode: None,
         }
     }
 }
 
+/// Scores are considered equal if they were set by the same user with the same score
+/// within two seconds of each other, regardless of their `score_id`.
+/// Use [`Score::same_play`] to compare by `score_id` whenever available.
 impl PartialEq for Score {
     fn eq(&self, other: &Self) -> bool {
         if self.user_id != other.user_id || self.score != other.score {
@@ -155,52 +167,152 @@ impl PartialEq for Score {
 impl Eq for Score {}
 
 impl Score {
+    /// Create a new score of the given user with the given score value.
+    ///
+    /// All other fields are set to their default.
+    pub fn new(user_id: u32, score: u32) -> Self {
+        Self {
+            user_id,
+            score,
+            ..Default::default()
+        }
+    }
+
+    /// The osu!api endpoint that leaderboard scores are retrieved from.
+    ///
+    /// Note that scores are also retrieved from `get_user_best` and `get_user_recent`.
+    #[inline]
+    pub fn source_endpoint() -> &'static str {
+        "get_scores"
+    }
+
     /// Retrieve the user of the score from the API.
-    /// Be sure to specify [`GameMode`] if necessary, defaults to `GameMode::Osu`.
+    ///
+    /// The user is requested in the mode the score was requested in, if specified.
+    /// Otherwise, be sure to specify [`GameMode`] if necessary, defaults to `GameMode::Osu`.
     pub fn get_user<'o>(&self, osu: &'o Osu) -> GetUser<'o> {
-        osu.user(self.user_id)
+        match self.mode {
+            Some(mode) => self.get_user_mode(osu, mode),
+            None => osu.user(self.user_id),
+        }
     }
 
-    /// Count all hitobjects of the score i.e. for `GameMode::Osu` the amount 300s, 100s, 50s, and misses.
-    pub fn total_hits(&self, mode: GameMode) -> u32 {
-        let mut amount = self.count300 + self.count100 + self.count_miss;
-
-        if mode != GameMode::Taiko {
-            amount += self.count50;
+    /// Retrieve the user of the score in the given mode from the API.
+    pub fn get_user_mode<'o>(&self, osu: &'o Osu, mode: GameMode) -> GetUser<'o> {
+        osu.user(self.user_id).mode(mode)
+    }
 
-            if mode != GameMode::Osu {
-                amount += self.count_katu;
-                amount += (mode != GameMode::Catch) as u32 * self.count_geki;
-            }
+    /// Check whether both scores stem from the same play.
+    ///
+    /// If both scores have a `score_id`, only those are compared.
+    /// Otherwise, e.g. for recent scores which come without id,
+    /// this falls back to the heuristic of the `PartialEq` implementation.
+    /// Prefer this over `==` when deduplicating scores of different endpoints.
+    pub fn same_play(&self, other: &Score) -> bool {
+        match (self.score_id, other.score_id) {
+            (Some(a), Some(b)) => a == b,
+            _ => self == other,
         }
+    }
 
-        amount
+    /// The time that passed since the score was set.
+    ///
+    /// Negative if the score's date lies in the future e.g. due to clock skew.
+    pub fn age(&self) -> time::Duration {
+        OffsetDateTime::now_utc() - self.date
+    }
+
+    #[cfg(feature = "chrono")]
+    /// The date of the score as `chrono` date.
+    pub fn date_chrono(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::chrono_compat::to_chrono(self.date)
+    }
+
+    /// Sort the scores by pp descendingly and pair each score with its weight
+    /// i.e. `0.95^i` for the `i`-th score, as used for a user's total pp.
+    pub fn weighted(scores: &[Score]) -> Vec<(Score, f32)> {
+        let mut scores = scores.to_vec();
+
+        scores.sort_by(|a, b| {
+            let a = a.pp.unwrap_or(0.0);
+            let b = b.pp.unwrap_or(0.0);
+
+            b.partial_cmp(&a).unwrap_or(Ordering::Equal)
+        });
+
+        let mut weight = 1.0;
+
+        scores
+            .into_iter()
+            .map(|score| {
+                let pair = (score, weight);
+                weight *= 0.95;
+
+                pair
+            })
+            .collect()
+    }
+
+    /// Count all hitobjects of the score i.e. for `GameMode::Osu` the amount 300s, 100s, 50s, and misses.
+    pub fn total_hits(&self, mode: GameMode) -> u32 {
+        HitCounts::from(self).total_hits(mode)
+    }
+
+    /// The key count of a `GameMode::Mania` score as specified by its key mod.
+    ///
+    /// `None` if no key mod is enabled, the key count is then determined by the map's CS.
+    pub fn mania_key_count(&self) -> Option<u8> {
+        self.enabled_mods.mania_key_count()
     }
 
     /// Calculate the accuracy i.e. `0 <= accuracy <= 100`
+    ///
+    /// For `GameMode::Catch`, the accuracy is the ratio of caught fruits, droplets,
+    /// and tiny droplets (`count300`, `count100`, `count50`) to all of them,
+    /// including misses and missed tiny droplets (`count_miss`, `count_katu`).
     pub fn accuracy(&self, mode: GameMode) -> f32 {
-        let amount_objects = self.total_hits(mode) as f32;
+        HitCounts::from(self).accuracy(mode)
+    }
 
-        let (numerator, denumerator) = match mode {
-            GameMode::Taiko => (
-                0.5 * self.count100 as f32 + self.count300 as f32,
-                amount_objects,
-            ),
-            GameMode::Catch => (
-                (self.count300 + self.count100 + self.count50) as f32,
-                amount_objects,
-            ),
-            GameMode::Osu | GameMode::Mania => {
-                let mut n = (self.count50 * 50 + self.count100 * 100 + self.count300 * 300) as f32;
+    /// Calculate the accuracy with the mode the score was requested in,
+    /// `None` if the mode was not specified.
+    pub fn accuracy_auto(&self) -> Option<f32> {
+        self.mode.map(|mode| self.accuracy(mode))
+    }
 
-                n += ((mode == GameMode::Mania) as u32
-                    * (self.count_katu * 200 + self.count_geki * 300)) as f32;
+    /// The ratio of the score's max combo to the given max combo of its map,
+    /// e.g. `0.998` for 99.8% of the max combo.
+    ///
+    /// Capped at `1.0` and `0.0` if the map's max combo is `0`.
+    pub fn combo_ratio(&self, map_max_combo: u32) -> f32 {
+        if map_max_combo == 0 {
+            return 0.0;
+        }
 
-                (n, amount_objects * 300.0)
-            }
+        (self.max_combo as f32 / map_max_combo as f32).min(1.0)
+    }
+
+    /// Approximate the full combo version of the score, e.g. to estimate its pp
+    /// with an external performance calculator.
+    ///
+    /// The returned score has its misses counted as 300s, is flagged as `perfect`,
+    /// and its grade is recalculated for the given mode.
+    /// Since the beatmap's max combo is unknown, `max_combo` is kept as is.
+    /// The pp value is cleared as it no longer applies.
+    /// Note that this is only an approximation as misses that turned into 300s
+    /// might have been 100s or 50s instead.
+    pub fn as_if_fc(&self, mode: GameMode) -> Score {
+        let mut score = Score {
+            count300: self.count300 + self.count_miss,
+            count_miss: 0,
+            perfect: true,
+            pp: None,
+            ..self.clone()
         };
 
-        (10_000.0 * numerator / denumerator).round() / 100.0
+        score.recalculate_grade(mode, None);
+
+        score
     }
 
     /// Recalculate the grade of the score. This method will both change the
@@ -311,7 +423,8 @@ impl Score {
     fn ctb_grade(&self, accuracy: Option<f32>) -> Grade {
         let accuracy = accuracy.unwrap_or_else(|| self.accuracy(GameMode::Catch));
 
-        if (100.0 - accuracy).abs() <= std::f32::EPSILON {
+        // The accuracy is rounded so instead check for missed fruits, droplets, and tiny droplets
+        if self.total_hits(GameMode::Catch) > 0 && self.count_miss == 0 && self.count_katu == 0 {
             if self.enabled_mods.contains(GameMods::Hidden) {
                 Grade::XH
             } else {
@@ -335,19 +448,279 @@ impl Score {
     }
 }
 
+/// The hit counts of a [`Score`] or [`GameScore`](crate::model::GameScore)
+/// so that both calculate their total hits and accuracy the same way.
+pub(crate) struct HitCounts {
+    pub(crate) count300: u32,
+    pub(crate) count100: u32,
+    pub(crate) count50: u32,
+    pub(crate) count_miss: u32,
+    pub(crate) count_geki: u32,
+    pub(crate) count_katu: u32,
+}
+
+impl HitCounts {
+    pub(crate) fn total_hits(&self, mode: GameMode) -> u32 {
+        let mut amount = self.count300 + self.count100 + self.count_miss;
+
+        if mode != GameMode::Taiko {
+            amount += self.count50;
+
+            if mode != GameMode::Osu {
+                amount += self.count_katu;
+                amount += (mode != GameMode::Catch) as u32 * self.count_geki;
+            }
+        }
+
+        amount
+    }
+
+    pub(crate) fn accuracy(&self, mode: GameMode) -> f32 {
+        let amount_objects = self.total_hits(mode) as f32;
+
+        let (numerator, denumerator) = match mode {
+            GameMode::Taiko => (
+                0.5 * self.count100 as f32 + self.count300 as f32,
+                amount_objects,
+            ),
+            GameMode::Catch => (
+                (self.count300 + self.count100 + self.count50) as f32,
+                amount_objects,
+            ),
+            GameMode::Osu | GameMode::Mania => {
+                let mut n = (self.count50 * 50 + self.count100 * 100 + self.count300 * 300) as f32;
+
+                n += ((mode == GameMode::Mania) as u32
+                    * (self.count_katu * 200 + self.count_geki * 300)) as f32;
+
+                (n, amount_objects * 300.0)
+            }
+        };
+
+        (10_000.0 * numerator / denumerator).round() / 100.0
+    }
+}
+
+impl From<&Score> for HitCounts {
+    fn from(score: &Score) -> Self {
+        Self {
+            count300: score.count300,
+            count100: score.count100,
+            count50: score.count50,
+            count_miss: score.count_miss,
+            count_geki: score.count_geki,
+            count_katu: score.count_katu,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn score_total_hits() {
-        let mut score = Score::default();
-        score.count_geki = 456;
-        score.count300 = 123;
-        score.count_katu = 5;
+    fn score_new() {
+        let score = Score::new(2, 1_000_000);
+        assert_eq!(score.user_id, 2);
+        assert_eq!(score.score, 1_000_000);
+        assert_eq!(score.score_id, None);
+        assert_eq!(score.grade, Grade::F);
+    }
+
+    #[test]
+    fn score_taiko_accuracy() {
+        let mut score = Score {
+            count300: 100,
+            // Not part of taiko judgements
+            count50: 7,
+            count_geki: 30,
+            count_katu: 20,
+            ..Default::default()
+        };
+        assert_eq!(score.total_hits(GameMode::Taiko), 100);
+        assert!((score.accuracy(GameMode::Taiko) - 100.0).abs() <= f32::EPSILON);
+
+        score.count300 = 50;
         score.count100 = 50;
-        score.count50 = 2;
-        score.count_miss = 1;
+        assert_eq!(score.total_hits(GameMode::Taiko), 100);
+        assert!((score.accuracy(GameMode::Taiko) - 75.0).abs() <= f32::EPSILON);
+
+        score.count_miss = 100;
+        assert_eq!(score.total_hits(GameMode::Taiko), 200);
+        assert!((score.accuracy(GameMode::Taiko) - 37.5).abs() <= f32::EPSILON);
+    }
+
+    #[test]
+    fn score_ctb_grade_missed_droplet() {
+        let mut score = Score {
+            count300: 1000,
+            count100: 200,
+            count50: 98_800,
+            count_katu: 1,
+            ..Default::default()
+        };
+        assert!((score.accuracy(GameMode::Catch) - 100.0).abs() <= f32::EPSILON);
+        assert_eq!(score.recalculate_grade(GameMode::Catch, None), Grade::S);
+
+        score.count_katu = 0;
+        assert_eq!(score.recalculate_grade(GameMode::Catch, None), Grade::X);
+
+        score.enabled_mods = GameMods::Hidden;
+        assert_eq!(score.recalculate_grade(GameMode::Catch, None), Grade::XH);
+    }
+
+    #[test]
+    fn score_ctb_grade_empty() {
+        let mut score = Score::default();
+        assert_eq!(score.recalculate_grade(GameMode::Catch, None), Grade::D);
+
+        score.enabled_mods = GameMods::Hidden;
+        assert_eq!(score.recalculate_grade(GameMode::Catch, None), Grade::D);
+    }
+
+    #[test]
+    fn score_combo_ratio() {
+        let score = Score {
+            max_combo: 998,
+            ..Default::default()
+        };
+
+        assert!((score.combo_ratio(1000) - 0.998).abs() <= f32::EPSILON);
+        assert!((score.combo_ratio(500) - 1.0).abs() <= f32::EPSILON);
+        assert!(score.combo_ratio(0).abs() <= f32::EPSILON);
+    }
+
+    #[test]
+    fn score_as_if_fc() {
+        let score = Score {
+            count300: 400,
+            count100: 20,
+            count_miss: 3,
+            max_combo: 321,
+            pp: Some(123.4),
+            grade: Grade::A,
+            ..Default::default()
+        };
+
+        let fc = score.as_if_fc(GameMode::Osu);
+        assert_eq!(fc.count300, 403);
+        assert_eq!(fc.count100, 20);
+        assert_eq!(fc.count_miss, 0);
+        assert_eq!(fc.max_combo, 321);
+        assert!(fc.perfect);
+        assert_eq!(fc.pp, None);
+        assert_eq!(fc.grade, Grade::S);
+        assert_eq!(
+            fc.total_hits(GameMode::Osu),
+            score.total_hits(GameMode::Osu)
+        );
+    }
+
+    #[test]
+    fn score_same_play() {
+        let date = OffsetDateTime::now_utc();
+
+        let score = Score {
+            score_id: Some(1),
+            user_id: 2,
+            score: 1_000_000,
+            date,
+            ..Default::default()
+        };
+
+        let other = Score {
+            score_id: Some(2),
+            date: date + time::Duration::seconds(1),
+            ..score.clone()
+        };
+        assert_eq!(score, other);
+        assert!(!score.same_play(&other));
+
+        let without_id = Score {
+            score_id: None,
+            ..other.clone()
+        };
+        assert!(score.same_play(&without_id));
+        assert!(without_id.same_play(&score));
+
+        let later = Score {
+            date: date + time::Duration::seconds(5),
+            ..without_id
+        };
+        assert!(!score.same_play(&later));
+        assert!(other.same_play(&other));
+    }
+
+    #[test]
+    fn score_mania_key_count() {
+        let mut score = Score::new(2, 1_000_000);
+        assert_eq!(score.mania_key_count(), None);
+
+        score.enabled_mods = GameMods::Key7 | GameMods::Hidden;
+        assert_eq!(score.mania_key_count(), Some(7));
+    }
+
+    #[test]
+    fn score_total_hits() {
+        let score = Score {
+            count_geki: 456,
+            count300: 123,
+            count_katu: 5,
+            count100: 50,
+            count50: 2,
+            count_miss: 1,
+            ..Default::default()
+        };
         assert_eq!(score.total_hits(GameMode::Osu), 123 + 50 + 2 + 1);
     }
+
+    #[test]
+    fn score_age() {
+        let score = Score {
+            date: OffsetDateTime::now_utc() - time::Duration::hours(2),
+            ..Default::default()
+        };
+
+        let age = score.age();
+        assert!(age >= time::Duration::hours(2));
+        assert!(age < time::Duration::hours(2) + time::Duration::minutes(1));
+    }
+
+    #[test]
+    fn score_source_endpoint() {
+        assert_eq!(Score::source_endpoint(), "get_scores");
+    }
+
+    #[test]
+    fn score_weighted() {
+        let scores: Vec<_> = [100.0, 300.0, 200.0]
+            .iter()
+            .map(|&pp| Score {
+                pp: Some(pp),
+                ..Default::default()
+            })
+            .collect();
+
+        let weighted = Score::weighted(&scores);
+        assert_eq!(weighted.len(), 3);
+        assert_eq!(weighted[0].0.pp, Some(300.0));
+        assert!((weighted[0].1 - 1.0).abs() < f32::EPSILON);
+        assert_eq!(weighted[1].0.pp, Some(200.0));
+        assert!((weighted[1].1 - 0.95).abs() < f32::EPSILON);
+        assert_eq!(weighted[2].0.pp, Some(100.0));
+    }
+
+    #[test]
+    fn score_accuracy_catch() {
+        let score = Score {
+            count300: 1000,
+            count100: 50,
+            count50: 200,
+            count_katu: 10,
+            count_miss: 5,
+            ..Default::default()
+        };
+        assert_eq!(score.total_hits(GameMode::Catch), 1265);
+        assert!((score.accuracy(GameMode::Catch) - 98.81).abs() < f32::EPSILON);
+    }
 }
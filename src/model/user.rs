@@ -1,17 +1,23 @@
 use crate::{
-    request::{GetUserBest, GetUserRecent},
+    model::{GameMode, Score},
+    request::{GetBeatmaps, GetUserBest, GetUserRecent},
     serde::*,
     Osu,
 };
 use serde::Deserialize;
+use std::fmt::{Display, Formatter, Result as FmtResult};
 
 #[cfg(feature = "serialize")]
 use serde::Serialize;
 use time::OffsetDateTime;
 
 /// User struct retrieved from the `/api/get_user` endpoint.
+///
+/// Marked as `#[non_exhaustive]` so that fields can be added without a breaking change.
+/// Create users through [`User::new`] and set further fields afterwards.
 #[derive(Debug, Clone, Deserialize)]
 #[cfg_attr(feature = "serialize", derive(Serialize))]
+#[non_exhaustive]
 pub struct User {
     #[serde(deserialize_with = "to_u32")]
     pub user_id: u32,
@@ -55,9 +61,29 @@ pub struct User {
     pub pp_country_rank: u32,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub events: Vec<Event>,
+    /// The mode the user was requested in, `None` if it was not specified.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<GameMode>,
 }
 
 impl User {
+    /// Create a new user with the given id and name.
+    ///
+    /// All other fields are set to their default.
+    pub fn new(user_id: u32, username: impl Into<String>) -> Self {
+        Self {
+            user_id,
+            username: username.into(),
+            ..Default::default()
+        }
+    }
+
+    /// The osu!api endpoint that users are retrieved from.
+    #[inline]
+    pub fn source_endpoint() -> &'static str {
+        "get_user"
+    }
+
     /// Retrieve the user's top scores from the API.
     /// Amount ranges from 1 to 100, defaults to 10.
     /// Be sure to specify [`GameMode`] if necessary, defaults to `GameMode::Osu`.
@@ -76,6 +102,89 @@ impl User {
         osu.recent_scores(self.user_id)
     }
 
+    /// Retrieve the beatmaps created by the user from the API.
+    ///
+    /// The api returns at most 500 beatmaps per request so for prolific mappers
+    /// be sure to page through them via [`since`](GetBeatmaps::since)
+    /// or use [`Osu::beatmaps_collect`].
+    pub fn get_beatmaps<'o>(&self, osu: &'o Osu) -> GetBeatmaps<'o> {
+        osu.beatmaps().creator(self.user_id)
+    }
+
+    #[cfg(feature = "chrono")]
+    /// The join date of the user as `chrono` date.
+    pub fn join_date_chrono(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::chrono_compat::to_chrono(self.join_date)
+    }
+
+    /// Capture the user's current rank and pp so that a
+    /// history can be built by taking snapshots over time.
+    pub fn snapshot(&self) -> RankSnapshot {
+        RankSnapshot {
+            date: OffsetDateTime::now_utc(),
+            pp_rank: self.pp_rank,
+            pp_raw: self.pp_raw,
+        }
+    }
+
+    /// Whether the user has a rank and pp, i.e. is not an inactive or restricted player.
+    pub fn is_ranked(&self) -> bool {
+        self.pp_rank != 0 && self.pp_raw > 0.0
+    }
+
+    /// The url of the user's avatar e.g. `https://a.ppy.sh/2`.
+    #[inline]
+    pub fn avatar_url(&self) -> String {
+        format!("https://a.ppy.sh/{}", self.user_id)
+    }
+
+    /// The url of the user's profile e.g. `https://osu.ppy.sh/u/2`.
+    #[inline]
+    pub fn profile_url(&self) -> String {
+        format!("https://osu.ppy.sh/u/{}", self.user_id)
+    }
+
+    /// The url of the user's profile for the given mode e.g. `https://osu.ppy.sh/u/2/taiko`.
+    #[inline]
+    pub fn profile_url_mode(&self, mode: GameMode) -> String {
+        // The website calls the catch mode `fruits`
+        let mode = match mode {
+            GameMode::Osu => "osu",
+            GameMode::Taiko => "taiko",
+            GameMode::Catch => "fruits",
+            GameMode::Mania => "mania",
+        };
+
+        format!("https://osu.ppy.sh/u/{}/{}", self.user_id, mode)
+    }
+
+    /// The average amount of plays per day since the user joined.
+    ///
+    /// `None` if the join date is not in the past.
+    pub fn plays_per_day(&self) -> Option<f32> {
+        let days = (OffsetDateTime::now_utc() - self.join_date).as_seconds_f32() / 86_400.0;
+
+        (days > 0.0).then(|| self.playcount as f32 / days)
+    }
+
+    /// The amount of SS grades, both with and without `Hidden` or `Flashlight`.
+    #[inline]
+    pub fn total_ss(&self) -> u32 {
+        self.count_ssh + self.count_ss
+    }
+
+    /// The amount of S grades, both with and without `Hidden` or `Flashlight`.
+    #[inline]
+    pub fn total_s(&self) -> u32 {
+        self.count_sh + self.count_s
+    }
+
+    /// The amount of all SS, S, and A grades.
+    #[inline]
+    pub fn ranked_grade_count(&self) -> u32 {
+        self.total_ss() + self.total_s() + self.count_a
+    }
+
     /// Count all 300s, 100s, and 50s of a user
     #[inline]
     pub fn total_hits(&self) -> u64 {
@@ -108,6 +217,7 @@ impl Default for User {
             total_seconds_played: 0,
             pp_country_rank: 0,
             events: Vec::default(),
+            mode: None,
         }
     }
 }
@@ -121,6 +231,34 @@ impl PartialEq for User {
 
 impl Eq for User {}
 
+/// Formats as `username (#rank)`.
+impl Display for User {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{} (#{})", self.username, self.pp_rank)
+    }
+}
+
+/// A [`User`] together with their top and recent scores, see [`GetUser::bundle`].
+///
+/// [`GetUser::bundle`]: crate::request::GetUser::bundle
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct UserBundle {
+    pub user: User,
+    pub top: Vec<Score>,
+    pub recent: Vec<Score>,
+}
+
+/// The rank and pp of a [`User`] at a point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct RankSnapshot {
+    #[serde(with = "serde_date")]
+    pub date: OffsetDateTime,
+    pub pp_rank: u32,
+    pub pp_raw: f32,
+}
+
 /// Event struct for events within the [`User`] struct.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
 #[cfg_attr(feature = "serialize", derive(Serialize))]
@@ -168,12 +306,133 @@ impl Event {
 mod tests {
     use super::*;
 
+    #[test]
+    fn user_new() {
+        let user = User::new(2, "Badewanne3");
+        assert_eq!(user.user_id, 2);
+        assert_eq!(user.username, "Badewanne3");
+        assert_eq!(user.pp_rank, 0);
+        assert!(user.events.is_empty());
+    }
+
+    #[test]
+    fn user_display() {
+        let user = User {
+            username: "Badewanne3".to_owned(),
+            pp_rank: 1234,
+            ..Default::default()
+        };
+        assert_eq!(user.to_string(), "Badewanne3 (#1234)");
+        assert_eq!(format!("{:#}", user), "Badewanne3 (#1234)");
+    }
+
     #[test]
     fn user_total_hits() {
-        let mut user = User::default();
-        user.count300 = 123;
-        user.count100 = 50;
-        user.count50 = 2;
+        let user = User {
+            count300: 123,
+            count100: 50,
+            count50: 2,
+            ..Default::default()
+        };
         assert_eq!(user.total_hits(), 123 + 50 + 2);
     }
+
+    #[test]
+    fn user_grade_counts() {
+        let user = User {
+            count_ssh: 1,
+            count_ss: 2,
+            count_sh: 30,
+            count_s: 40,
+            count_a: 500,
+            ..Default::default()
+        };
+        assert_eq!(user.total_ss(), 3);
+        assert_eq!(user.total_s(), 70);
+        assert_eq!(user.ranked_grade_count(), 573);
+    }
+
+    #[test]
+    fn user_urls() {
+        let user = User::new(2, "Badewanne3");
+        assert_eq!(user.avatar_url(), "https://a.ppy.sh/2");
+        assert_eq!(user.profile_url(), "https://osu.ppy.sh/u/2");
+        assert_eq!(
+            user.profile_url_mode(GameMode::Osu),
+            "https://osu.ppy.sh/u/2/osu"
+        );
+        assert_eq!(
+            user.profile_url_mode(GameMode::Catch),
+            "https://osu.ppy.sh/u/2/fruits"
+        );
+    }
+
+    #[test]
+    fn user_source_endpoint() {
+        assert_eq!(User::source_endpoint(), "get_user");
+    }
+
+    #[test]
+    fn event_epic_factor() {
+        let json = r#"{
+            "display_html": "<b><a href='/u/2'>Badewanne3</a></b> achieved rank #1",
+            "beatmap_id": "905576",
+            "beatmapset_id": "421993",
+            "date": "2020-05-01 12:34:56",
+            "epicfactor": "5"
+        }"#;
+        let event: Event = serde_json::from_str(json).unwrap();
+        assert_eq!(event.epic_factor, 5);
+
+        let json = json.replace(r#""epicfactor": "5""#, r#""epicfactor": 5"#);
+        let event: Event = serde_json::from_str(&json).unwrap();
+        assert_eq!(event.epic_factor, 5);
+    }
+
+    #[test]
+    fn user_snapshot() {
+        let user = User {
+            pp_rank: 4321,
+            pp_raw: 1234.5,
+            ..Default::default()
+        };
+        let before = OffsetDateTime::now_utc();
+        let snapshot = user.snapshot();
+        assert_eq!(snapshot.pp_rank, 4321);
+        assert!((snapshot.pp_raw - 1234.5).abs() < f32::EPSILON);
+        assert!(snapshot.date >= before);
+    }
+
+    #[test]
+    fn user_is_ranked() {
+        let mut user = User {
+            pp_rank: 4321,
+            pp_raw: 1234.5,
+            ..Default::default()
+        };
+        assert!(user.is_ranked());
+
+        user.pp_rank = 0;
+        assert!(!user.is_ranked());
+
+        user.pp_rank = 4321;
+        user.pp_raw = 0.0;
+        assert!(!user.is_ranked());
+    }
+
+    #[test]
+    fn user_plays_per_day() {
+        let user = User {
+            playcount: 500,
+            join_date: OffsetDateTime::now_utc() - time::Duration::days(10),
+            ..Default::default()
+        };
+        assert!((user.plays_per_day().unwrap() - 50.0).abs() < 0.01);
+
+        let user = User {
+            join_date: OffsetDateTime::now_utc() + time::Duration::days(1),
+            ..user
+        };
+        assert_eq!(user.plays_per_day(), None);
+    }
 }
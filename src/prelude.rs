@@ -1,7 +1,7 @@
 pub use crate::{
     error::{ApiError, ModError},
     model::*,
-    Osu, OsuError, OsuResult,
+    Osu, OsuError, OsuResult, RatelimitInfo, ResultExt,
 };
 
 pub use reqwest::ClientBuilder;
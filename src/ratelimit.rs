@@ -1,12 +1,58 @@
-use std::time::{Duration, Instant};
-use tokio::{sync::Mutex, time::sleep};
+use reqwest::header::HeaderMap;
+use std::{str::FromStr, sync::Mutex as StdMutex, time::Duration};
+use tokio::{
+    sync::Mutex,
+    time::{sleep, Instant},
+};
+
+#[cfg(feature = "metrics")]
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const REMAINING_HEADER: &str = "X-RateLimit-Remaining";
+const RESET_HEADER: &str = "X-RateLimit-Reset";
+
+/// Ratelimit information of the most recent response.
+///
+/// The osu!api v1 does not document ratelimit headers but some proxies add them.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct RatelimitInfo {
+    /// Value of the `X-RateLimit-Remaining` header
+    pub remaining: Option<u32>,
+    /// Value of the `X-RateLimit-Reset` header
+    pub reset: Option<u64>,
+}
+
+impl RatelimitInfo {
+    /// Parse the ratelimit headers, `None` if neither of them is present.
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let info = Self {
+            remaining: parse_header(headers, REMAINING_HEADER),
+            reset: parse_header(headers, RESET_HEADER),
+        };
+
+        (info != Self::default()).then_some(info)
+    }
+}
+
+fn parse_header<T: FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+}
 
 /// Basic ratelimiter that grants access for a certain amount of times within a time span.
 /// Implemented through token bucket algorithm.
 pub(crate) struct RateLimiter {
+    enabled: bool,
     rate: f32,
     rate_per_ms: f32,
     guarded: Mutex<Inner>,
+    last_info: StdMutex<Option<RatelimitInfo>>,
+    #[cfg(feature = "metrics")]
+    delayed: AtomicU64,
+    #[cfg(feature = "metrics")]
+    immediate: AtomicU64,
 }
 
 struct Inner {
@@ -19,17 +65,63 @@ impl RateLimiter {
     /// Allows for up to `rate` amount of access calls within `per_seconds` amount of seconds.
     pub(crate) fn new(rate: u32, per_seconds: u32) -> Self {
         Self {
+            enabled: true,
             rate: rate as f32,
             rate_per_ms: rate as f32 / per_seconds as f32 / 1000.0,
             guarded: Mutex::new(Inner {
                 allowance: 0.0,
                 last_call: Instant::now(),
             }),
+            last_info: StdMutex::new(None),
+            #[cfg(feature = "metrics")]
+            delayed: AtomicU64::new(0),
+            #[cfg(feature = "metrics")]
+            immediate: AtomicU64::new(0),
+        }
+    }
+
+    /// Creates a [`RateLimiter`] that grants every access immediately.
+    pub(crate) fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::new(1, 1)
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    /// The amount of access calls that were delayed and that were granted immediately.
+    pub(crate) fn stats(&self) -> (u64, u64) {
+        (
+            self.delayed.load(Ordering::Relaxed),
+            self.immediate.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Store the ratelimit information of a response if it contains any.
+    pub(crate) fn observe(&self, headers: &HeaderMap) {
+        if let Some(info) = RatelimitInfo::from_headers(headers) {
+            if let Ok(mut last_info) = self.last_info.lock() {
+                last_info.replace(info);
+            }
         }
     }
 
+    /// The most recently observed ratelimit information.
+    pub(crate) fn last_info(&self) -> Option<RatelimitInfo> {
+        self.last_info.lock().ok().and_then(|info| *info)
+    }
+
     /// Wait until the next access
+    ///
+    /// Cancellation safe: if the future is dropped while waiting, no access is consumed.
     pub(crate) async fn await_access(&self) {
+        if !self.enabled {
+            #[cfg(feature = "metrics")]
+            self.immediate.fetch_add(1, Ordering::Relaxed);
+
+            return;
+        }
+
         let mut guarded = self.guarded.lock().await;
 
         let Inner {
@@ -40,16 +132,85 @@ impl RateLimiter {
         let elapsed = last_call.elapsed().as_millis() as f32; // ms
         *allowance += elapsed * self.rate_per_ms; // msgs
 
+        // Account for the elapsed time right away so that it is not added
+        // a second time if this future is dropped while sleeping
+        *last_call = Instant::now();
+
         if *allowance > self.rate {
             *allowance = self.rate - 1.0;
+
+            #[cfg(feature = "metrics")]
+            self.immediate.fetch_add(1, Ordering::Relaxed);
         } else if *allowance < 1.0 {
             let ms_left = (1.0 - *allowance) / self.rate_per_ms; // s
             sleep(Duration::from_micros((1000.0 * ms_left).round() as u64)).await;
             *allowance = 0.0;
+
+            #[cfg(feature = "metrics")]
+            self.delayed.fetch_add(1, Ordering::Relaxed);
         } else {
             *allowance -= 1.0;
+
+            #[cfg(feature = "metrics")]
+            self.immediate.fetch_add(1, Ordering::Relaxed);
         }
 
         *last_call = Instant::now();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn ratelimiter_cancelled_access() {
+        let ratelimiter = RateLimiter::new(1, 1);
+        ratelimiter.await_access().await;
+
+        // Drop the access while it is still waiting
+        sleep(Duration::from_millis(300)).await;
+        let pending = tokio::time::timeout(Duration::from_millis(200), ratelimiter.await_access());
+        assert!(pending.await.is_err());
+
+        // The cancelled access neither consumed nor double counted the allowance
+        let start = Instant::now();
+        ratelimiter.await_access().await;
+        let waited = start.elapsed();
+        assert!(waited >= Duration::from_millis(499), "{:?}", waited);
+        assert!(waited <= Duration::from_millis(501), "{:?}", waited);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn ratelimiter_disabled() {
+        let ratelimiter = RateLimiter::disabled();
+
+        let start = Instant::now();
+
+        for _ in 0..100 {
+            ratelimiter.await_access().await;
+        }
+
+        // Time only advances while waiting so no access was delayed
+        assert_eq!(start.elapsed(), Duration::ZERO);
+    }
+
+    #[cfg(feature = "metrics")]
+    #[tokio::test]
+    async fn ratelimiter_stats() {
+        let ratelimiter = RateLimiter::new(2, 1);
+        assert_eq!(ratelimiter.stats(), (0, 0));
+
+        // Drain the initial allowance
+        ratelimiter.await_access().await;
+        let (delayed, immediate) = ratelimiter.stats();
+        assert_eq!(delayed + immediate, 1);
+
+        // Back-to-back calls never build up a full access worth of allowance
+        for _ in 0..5 {
+            ratelimiter.await_access().await;
+        }
+
+        assert_eq!(ratelimiter.stats(), (delayed + 5, immediate));
+    }
+}
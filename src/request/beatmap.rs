@@ -1,10 +1,11 @@
+use std::collections::HashSet;
 use time::OffsetDateTime;
 
 use super::{Pending, UserIdentification};
 use crate::{
     model::{Beatmap, GameMode, GameMods},
     routing::Route,
-    Osu,
+    Osu, OsuResult,
 };
 
 /// Retrieve a [`Beatmap`].
@@ -37,10 +38,19 @@ pub struct GetBeatmaps<'a> {
     mods: Option<GameMods>,
     since: Option<OffsetDateTime>,
     with_converted: Option<bool>,
+    stars: Option<(f32, f32)>,
+    order: Option<BeatmapOrder>,
+}
+
+/// Order in which [`GetBeatmaps`] sorts the retrieved maps.
+#[derive(Copy, Clone)]
+enum BeatmapOrder {
+    Stars,
+    Date,
 }
 
 macro_rules! impl_beatmap {
-    ($name: ident, $default_limit: expr) => {
+    ($name: ident, $default_limit: expr $(, $field: ident: $value: expr)*) => {
         impl<'a> $name<'a> {
             pub(crate) fn new(osu: &'a Osu) -> Self {
                 Self {
@@ -55,6 +65,7 @@ macro_rules! impl_beatmap {
                     mods: None,
                     since: None,
                     with_converted: None,
+                    $($field: $value,)*
                 }
             }
 
@@ -78,7 +89,7 @@ macro_rules! impl_beatmap {
             /// Default and maximum are 500.
             #[inline]
             pub fn limit(mut self, limit: u32) -> Self {
-                self.limit.replace(limit.max(0).min(500));
+                self.limit.replace(limit.min(GetBeatmaps::MAX_LIMIT));
 
                 self
             }
@@ -116,6 +127,14 @@ macro_rules! impl_beatmap {
                 self
             }
 
+            /// Reset previously specified [`mods`](Self::mods), e.g. when reusing a builder.
+            #[inline]
+            pub fn clear_mods(mut self) -> Self {
+                self.mods.take();
+
+                self
+            }
+
             /// Optional, only ranked/loved beatmaps approved since this date.
             #[inline]
             pub fn since(mut self, since: OffsetDateTime) -> Self {
@@ -135,18 +154,44 @@ macro_rules! impl_beatmap {
                 self
             }
 
+            /// Optional, request the beatmap converted to the given mode,
+            /// showing its converted difficulty rating.
+            ///
+            /// Sets both the mode and `with_converted(true)`.
+            /// Maps can not be converted to `GameMode::Osu` so in that case only the mode is set.
+            #[inline]
+            pub fn as_converted(mut self, mode: GameMode) -> Self {
+                if mode != GameMode::Osu {
+                    self.with_converted.replace(true);
+                }
+
+                self.mode(mode)
+            }
+
+            pub(crate) fn route(&self) -> Route {
+                Route::GetBeatmaps {
+                    creator: self.creator.clone(),
+                    hash: self.hash.as_deref().map(Box::from),
+                    limit: self.limit,
+                    map_id: self.map_id,
+                    mapset_id: self.mapset_id,
+                    mode: self.mode,
+                    mods: self.mods,
+                    since: self.since,
+                    with_converted: self.with_converted,
+                }
+            }
+            /// Whether the retrieved maps are converted,
+            /// `None` if it can not be inferred from the request.
+            fn converted(&self) -> Option<bool> {
+                match self.mode {
+                    Some(mode) if mode != GameMode::Osu && self.with_converted == Some(true) => None,
+                    _ => Some(false),
+                }
+            }
+
             fn start(&mut self) {
-                let route = Route::GetBeatmaps {
-                    creator: self.creator.take(),
-                    hash: self.hash.take().map(String::into_boxed_str),
-                    limit: self.limit.take(),
-                    map_id: self.map_id.take(),
-                    mapset_id: self.mapset_id.take(),
-                    mode: self.mode.take(),
-                    mods: self.mods.take(),
-                    since: self.since.take(),
-                    with_converted: self.with_converted.take(),
-                };
+                let route = self.route();
 
                 #[cfg(feature = "metrics")]
                 self.osu.unwrap().0.metrics.beatmaps.inc();
@@ -158,8 +203,128 @@ macro_rules! impl_beatmap {
     };
 }
 
-impl_beatmap!(GetBeatmaps, None);
-poll_vec_req!(GetBeatmaps<'_>, Beatmap);
+impl_beatmap!(GetBeatmaps, None, stars: None, order: None);
+poll_vec_req!(GetBeatmaps<'_>, Beatmap, post_process);
+route_uri!(GetBeatmaps<'_>);
+
+impl<'a> GetBeatmaps<'a> {
+    /// Maximum amount of beatmaps that can be requested at once.
+    pub const MAX_LIMIT: u32 = 500;
+
+    /// Optional, only keep beatmaps whose star rating is between `min` and `max`, inclusively.
+    ///
+    /// The filter is applied after the request so the result may contain
+    /// fewer beatmaps than the specified limit.
+    /// If mods are specified, the filter applies to the adjusted star rating.
+    #[inline]
+    pub fn stars_between(mut self, min: f32, max: f32) -> Self {
+        self.stars.replace((min, max));
+
+        self
+    }
+
+    /// Optional, sort the retrieved beatmaps by their star rating, ascendingly.
+    ///
+    /// Beatmaps with an invalid star rating i.e. `NaN` come last.
+    #[inline]
+    pub fn sorted_by_stars(mut self) -> Self {
+        self.order.replace(BeatmapOrder::Stars);
+
+        self
+    }
+
+    /// Optional, sort the retrieved beatmaps by their submit date, oldest first.
+    #[inline]
+    pub fn sorted_by_date(mut self) -> Self {
+        self.order.replace(BeatmapOrder::Date);
+
+        self
+    }
+
+    /// Request pages of up to 500 beatmaps with the same parameters until
+    /// `total` beatmaps are collected or no more beatmaps are available.
+    ///
+    /// Each page starts at the approved date of the previous page's last beatmap.
+    pub(crate) async fn collect(mut self, osu: &Osu, total: usize) -> OsuResult<Vec<Beatmap>> {
+        let mut maps = Vec::with_capacity(total);
+        let mut seen = HashSet::new();
+        let mut since = self.since;
+
+        while maps.len() < total {
+            let page = self.page(osu, since).await?;
+            let exhausted = page.len() < Self::MAX_LIMIT as usize;
+            let last_date = page.last().and_then(|map| map.approved_date);
+            let prev_len = maps.len();
+
+            maps.extend(page.into_iter().filter(|map| seen.insert(map.beatmap_id)));
+
+            // Stop if a page only contained known maps to avoid requesting it over and over
+            if exhausted || maps.len() == prev_len || last_date.is_none() {
+                break;
+            }
+
+            since = last_date;
+        }
+
+        maps.truncate(total);
+
+        self.post_process(maps)
+    }
+
+    fn page<'o>(&self, osu: &'o Osu, since: Option<OffsetDateTime>) -> GetBeatmaps<'o> {
+        GetBeatmaps {
+            fut: None,
+            osu: Some(osu),
+            creator: self.creator.clone(),
+            hash: self.hash.clone(),
+            limit: Some(Self::MAX_LIMIT),
+            map_id: self.map_id,
+            mapset_id: self.mapset_id,
+            mode: self.mode,
+            mods: self.mods,
+            since,
+            with_converted: self.with_converted,
+            stars: None,
+            order: None,
+        }
+    }
+
+    fn post_process(&mut self, mut maps: Vec<Beatmap>) -> OsuResult<Vec<Beatmap>> {
+        let converted = self.converted();
+
+        for map in maps.iter_mut() {
+            map.converted = converted;
+        }
+
+        if let Some((min, max)) = self.stars {
+            maps.retain(|map| min <= map.stars && map.stars <= max);
+        }
+
+        match self.order {
+            Some(BeatmapOrder::Stars) => maps.sort_by(|a, b| {
+                a.stars
+                    .is_nan()
+                    .cmp(&b.stars.is_nan())
+                    .then_with(|| a.stars.total_cmp(&b.stars))
+            }),
+            Some(BeatmapOrder::Date) => maps.sort_by_key(|map| map.submit_date),
+            None => {}
+        }
+
+        Ok(maps)
+    }
+}
 
 impl_beatmap!(GetBeatmap, Some(1));
-poll_req!(GetBeatmap<'_>, Beatmap);
+poll_req!(GetBeatmap<'_>, Beatmap, post_process);
+route_uri!(GetBeatmap<'_>);
+
+impl GetBeatmap<'_> {
+    fn post_process(&mut self, mut map: Option<Beatmap>) -> OsuResult<Option<Beatmap>> {
+        if let Some(map) = map.as_mut() {
+            map.converted = self.converted();
+        }
+
+        Ok(map)
+    }
+}
@@ -1,4 +1,4 @@
-use super::Pending;
+use super::{parse_json, Pending};
 use crate::{model::Match, routing::Route, Osu, OsuError, OsuResult};
 
 /// Retrieve a [`Match`].
@@ -19,10 +19,14 @@ impl<'a> GetMatch<'a> {
         }
     }
 
-    fn start(&mut self) {
-        let route = Route::GetMatch {
+    fn route(&self) -> Route {
+        Route::GetMatch {
             match_id: self.match_id,
-        };
+        }
+    }
+
+    fn start(&mut self) {
+        let route = self.route();
 
         #[cfg(feature = "metrics")]
         self.osu.0.metrics.matches.inc();
@@ -31,6 +35,8 @@ impl<'a> GetMatch<'a> {
     }
 }
 
+route_uri!(GetMatch<'_>);
+
 impl<'a> std::future::Future for GetMatch<'a> {
     type Output = OsuResult<Match>;
 
@@ -48,13 +54,7 @@ impl<'a> std::future::Future for GetMatch<'a> {
                     Poll::Pending => return Poll::Pending,
                 };
 
-                let bytes = bytes.as_ref();
-
-                let value =
-                    serde_json::from_slice::<Match>(bytes).map_err(|source| OsuError::Parsing {
-                        body: String::from_utf8_lossy(bytes).into_owned(),
-                        source,
-                    });
+                let value = parse_json::<Match>(bytes.as_ref());
 
                 return Poll::Ready(value.map_err(|_| OsuError::InvalidMultiplayerMatch));
             } else {
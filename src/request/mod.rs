@@ -1,5 +1,5 @@
 macro_rules! poll_req {
-    ($ty: ty, $ret: ty) => {
+    ($ty: ty, $ret: ty $(, $post: ident)?) => {
         impl ::std::future::Future for $ty {
             type Output = $crate::error::OsuResult<Option<$ret>>;
 
@@ -7,9 +7,6 @@ macro_rules! poll_req {
                 mut self: ::std::pin::Pin<&mut Self>,
                 cx: &mut ::std::task::Context<'_>,
             ) -> ::std::task::Poll<Self::Output> {
-                use crate::serde::SingleItemVisitor;
-                use ::serde::de::Deserializer as _;
-                use ::serde_json::Deserializer as JsonDeserializer;
                 use ::std::task::Poll;
 
                 loop {
@@ -20,14 +17,9 @@ macro_rules! poll_req {
                             Poll::Pending => return Poll::Pending,
                         };
 
-                        let bytes = bytes.as_ref();
+                        let value = $crate::request::parse_single::<$ret>(bytes.as_ref());
 
-                        let value = JsonDeserializer::from_slice(bytes)
-                            .deserialize_seq(SingleItemVisitor::<$ret>::default())
-                            .map_err(|source| crate::OsuError::Parsing {
-                                body: String::from_utf8_lossy(bytes).into_owned(),
-                                source,
-                            });
+                        $(let value = value.and_then(|item| self.as_mut().$post(item));)?
 
                         return Poll::Ready(value);
                     } else {
@@ -40,7 +32,7 @@ macro_rules! poll_req {
 }
 
 macro_rules! poll_vec_req {
-    ($ty: ty, $ret: ty) => {
+    ($ty: ty, $ret: ty $(, $post: ident)?) => {
         impl ::std::future::Future for $ty {
             type Output = $crate::error::OsuResult<Vec<$ret>>;
 
@@ -57,13 +49,11 @@ macro_rules! poll_vec_req {
                             Poll::Pending => return Poll::Pending,
                         };
 
-                        let bytes = bytes.as_ref();
-                        return Poll::Ready(serde_json::from_slice(bytes).map_err(|source| {
-                            crate::OsuError::Parsing {
-                                body: String::from_utf8_lossy(bytes).into_owned(),
-                                source,
-                            }
-                        }));
+                        let value = $crate::request::parse_json::<Vec<$ret>>(bytes.as_ref());
+
+                        $(let value = value.and_then(|items| self.as_mut().$post(items));)?
+
+                        return Poll::Ready(value);
                     } else {
                         self.as_mut().start()
                     }
@@ -73,22 +63,43 @@ macro_rules! poll_vec_req {
     };
 }
 
+macro_rules! route_uri {
+    ($ty: ty) => {
+        impl $ty {
+            /// The uri of the request relative to the api's base url.
+            ///
+            /// Does not contain the api key so it is safe to log.
+            #[inline]
+            pub fn route_uri(&self) -> String {
+                $crate::request::Request::from(self.route())
+                    .uri()
+                    .to_owned()
+            }
+        }
+    };
+}
+
 mod beatmap;
 mod r#match;
+mod replay;
 mod score;
 mod user;
 mod user_score;
 
 pub use beatmap::{GetBeatmap, GetBeatmaps};
 pub use r#match::GetMatch;
+pub use replay::GetReplay;
 pub use score::{GetScore, GetScores};
-pub use user::GetUser;
+pub use user::{GetUser, GetUserBundle};
 pub use user_score::{GetUserBest, GetUserRecent};
 
-use crate::OsuResult;
+use crate::{serde::SingleItemVisitor, OsuError, OsuResult};
 
 use bytes::Bytes;
-use serde::{Deserialize, Serialize};
+use serde::{
+    de::{DeserializeOwned, Deserializer as _},
+    Deserialize, Serialize,
+};
 use std::{
     fmt::{Display, Formatter, Result as FmtResult},
     future::Future,
@@ -103,16 +114,94 @@ const USER_TAG: &str = "u";
 #[derive(Debug)]
 pub(crate) struct Request(pub(crate) Box<str>);
 
+impl Request {
+    /// The uri relative to the base url, without the api key.
+    ///
+    /// Safe to log since the key is only appended when the request is sent.
+    #[inline]
+    pub(crate) fn uri(&self) -> &str {
+        &self.0
+    }
+
+    /// The endpoint of the request e.g. `get_user`.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn endpoint(&self) -> &str {
+        self.0.split('?').next().unwrap_or_default()
+    }
+}
+
+/// Deserialize a json response body.
+#[cfg(not(feature = "simd-json"))]
+pub(crate) fn parse_json<T: DeserializeOwned>(bytes: &[u8]) -> OsuResult<T> {
+    serde_json::from_slice(bytes).map_err(|source| OsuError::Parsing {
+        body: String::from_utf8_lossy(bytes).into_owned(),
+        source,
+    })
+}
+
+/// Deserialize a json response body.
+///
+/// simd-json parses in-place so the bytes are copied into a mutable buffer first.
+#[cfg(feature = "simd-json")]
+pub(crate) fn parse_json<T: DeserializeOwned>(bytes: &[u8]) -> OsuResult<T> {
+    let mut buf = bytes.to_vec();
+
+    simd_json::from_slice(&mut buf).map_err(|source| OsuError::SimdParsing {
+        body: String::from_utf8_lossy(bytes).into_owned(),
+        source,
+    })
+}
+
+/// Deserialize the first item of a json array, if any.
+#[cfg(not(feature = "simd-json"))]
+pub(crate) fn parse_single<T: DeserializeOwned>(bytes: &[u8]) -> OsuResult<Option<T>> {
+    serde_json::Deserializer::from_slice(bytes)
+        .deserialize_seq(SingleItemVisitor::<T>::default())
+        .map_err(|source| OsuError::Parsing {
+            body: String::from_utf8_lossy(bytes).into_owned(),
+            source,
+        })
+}
+
+/// Deserialize the first item of a json array, if any.
+#[cfg(feature = "simd-json")]
+pub(crate) fn parse_single<T: DeserializeOwned>(bytes: &[u8]) -> OsuResult<Option<T>> {
+    let mut buf = bytes.to_vec();
+
+    simd_json::Deserializer::from_slice(&mut buf)
+        .and_then(|mut de| de.deserialize_seq(SingleItemVisitor::<T>::default()))
+        .map_err(|source| OsuError::SimdParsing {
+            body: String::from_utf8_lossy(bytes).into_owned(),
+            source,
+        })
+}
+
 /// Identifies a user either by id or by name.
 ///
 /// Not needed to use explicitely, only required as `Into<UserIdentification>` i.e. `u32`, `String`, `&str`, or `&String`.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum UserIdentification {
     Name(String),
     Id(u32),
 }
 
+impl UserIdentification {
+    /// Maximum amount of characters in a username.
+    const MAX_NAME_LEN: usize = 15;
+
+    /// Check whether the user identification could be valid.
+    /// Names must not be empty or longer than 15 characters, ids are always valid.
+    pub fn validate(&self) -> OsuResult<()> {
+        match self {
+            Self::Name(name) if name.is_empty() || name.chars().count() > Self::MAX_NAME_LEN => {
+                Err(OsuError::InvalidUsername(name.to_owned()))
+            }
+            Self::Name(_) | Self::Id(_) => Ok(()),
+        }
+    }
+}
+
 impl Display for UserIdentification {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self {
@@ -149,3 +238,106 @@ impl From<&String> for UserIdentification {
         Self::Name(name.to_owned())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "simd-json")]
+    use crate::model::Beatmap;
+
+    #[cfg(feature = "simd-json")]
+    fn beatmaps_payload(count: u32) -> String {
+        let maps: Vec<_> = (0..count)
+            .map(|i| {
+                format!(
+                    r#"{{
+                        "beatmapset_id": "{set}",
+                        "beatmap_id": "{id}",
+                        "approved": "1",
+                        "total_length": "167",
+                        "hit_length": "164",
+                        "version": "Insane \"{id}\"",
+                        "file_md5": "4f6b2e1a3ddb46ce59df8b8f4b3ac6b5",
+                        "diff_size": "4",
+                        "diff_overall": "8",
+                        "diff_approach": "9.{i}",
+                        "diff_drain": "5",
+                        "mode": "{mode}",
+                        "count_normal": "464",
+                        "count_slider": "229",
+                        "count_spinner": "1",
+                        "submit_date": "2019-12-23 11:01:42",
+                        "approved_date": "2020-02-06 16:40:23",
+                        "last_update": "2020-01-29 12:51:02",
+                        "artist": "Artist",
+                        "title": "Title",
+                        "creator": "Mao",
+                        "creator_id": "3178418",
+                        "bpm": "174",
+                        "source": "",
+                        "tags": "tag{i} other",
+                        "genre_id": "2",
+                        "language_id": "3",
+                        "favourite_count": "84",
+                        "rating": "9.26",
+                        "storyboard": "0",
+                        "video": "0",
+                        "download_unavailable": "0",
+                        "audio_unavailable": "0",
+                        "playcount": "{i}",
+                        "passcount": "10983",
+                        "max_combo": "1063",
+                        "diff_aim": "2.6457",
+                        "diff_speed": "2.3561",
+                        "difficultyrating": "5.{i}"
+                    }}"#,
+                    set = 1_000_000 + i / 4,
+                    id = 2_000_000 + i,
+                    i = i,
+                    mode = i % 4,
+                )
+            })
+            .collect();
+
+        format!("[{}]", maps.join(","))
+    }
+
+    #[cfg(feature = "simd-json")]
+    #[test]
+    fn parse_json_matches_serde_json() {
+        let payload = beatmaps_payload(2000);
+
+        let expected: Vec<Beatmap> = serde_json::from_str(&payload).unwrap();
+        let parsed: Vec<Beatmap> = parse_json(payload.as_bytes()).unwrap();
+
+        assert_eq!(parsed.len(), 2000);
+        assert_eq!(format!("{:?}", parsed), format!("{:?}", expected));
+
+        let single: Option<Beatmap> = parse_single(payload.as_bytes()).unwrap();
+        assert_eq!(format!("{:?}", single), format!("{:?}", expected.first()));
+        assert!(parse_single::<Beatmap>(b"[]").unwrap().is_none());
+    }
+
+    #[test]
+    fn max_limits() {
+        assert_eq!(GetBeatmaps::MAX_LIMIT, 500);
+        assert_eq!(GetScores::MAX_LIMIT, 100);
+        assert_eq!(GetUserBest::MAX_LIMIT, 100);
+        assert_eq!(GetUserRecent::MAX_LIMIT, 50);
+    }
+
+    #[test]
+    fn validate_user_identification() {
+        assert!(UserIdentification::from("Badewanne3").validate().is_ok());
+        assert!(UserIdentification::from(2).validate().is_ok());
+
+        let empty = UserIdentification::from("");
+        assert!(matches!(
+            empty.validate(),
+            Err(OsuError::InvalidUsername(_))
+        ));
+
+        let long = UserIdentification::from("a_very_long_username");
+        assert!(matches!(long.validate(), Err(OsuError::InvalidUsername(_))));
+    }
+}
@@ -0,0 +1,136 @@
+use super::{parse_json, Pending, UserIdentification};
+use crate::{
+    error::ApiError,
+    model::{GameMode, GameMods, Replay},
+    routing::Route,
+    Osu, OsuError, OsuResult,
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+/// Retrieve the [`Replay`] of a score.
+///
+/// Note that the api only allows 10 replay requests per minute.
+///
+/// [`Replay`]: crate::model::Replay
+pub struct GetReplay<'a> {
+    fut: Option<Pending<'a>>,
+    osu: &'a Osu,
+
+    map_id: u32,
+    mode: Option<GameMode>,
+    mods: Option<GameMods>,
+    user: Option<UserIdentification>,
+}
+
+/// The api responds with status 200 for both replay content and error messages.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ReplayResponse {
+    Content { content: String },
+    Error { error: String },
+}
+
+const REPLAY_UNAVAILABLE: &str = "Replay not available.";
+
+impl<'a> GetReplay<'a> {
+    #[inline]
+    pub(crate) fn new(osu: &'a Osu, map_id: u32, user: impl Into<UserIdentification>) -> Self {
+        Self {
+            fut: None,
+            osu,
+            map_id,
+            mode: None,
+            mods: None,
+            user: Some(user.into()),
+        }
+    }
+
+    /// Optional, defaults to `GameMode::Osu`.
+    #[inline]
+    pub fn mode(mut self, mode: GameMode) -> Self {
+        self.mode.replace(mode);
+
+        self
+    }
+
+    /// Optional, the mods of the score, useful if the user has multiple scores on the map.
+    #[inline]
+    pub fn mods(mut self, mods: GameMods) -> Self {
+        self.mods.replace(mods);
+
+        self
+    }
+
+    fn route(&self) -> Route {
+        Route::GetReplay {
+            map_id: self.map_id,
+            mode: self.mode,
+            mods: self.mods,
+            user: self.user.clone().unwrap(),
+        }
+    }
+
+    fn start(&mut self) {
+        let route = self.route();
+
+        #[cfg(feature = "metrics")]
+        self.osu.0.metrics.replays.inc();
+
+        self.fut.replace(Box::pin(self.osu.request_bytes(route)));
+    }
+
+    /// The api responds with an error message instead of content if no replay is available
+    /// or if the request was rejected otherwise e.g. due to the ratelimit.
+    fn decode(bytes: &[u8]) -> OsuResult<Replay> {
+        let content = match parse_json::<ReplayResponse>(bytes)? {
+            ReplayResponse::Content { content } => content,
+            ReplayResponse::Error { error } if error == REPLAY_UNAVAILABLE => {
+                return Err(OsuError::ReplayUnavailable)
+            }
+            ReplayResponse::Error { error } => {
+                return Err(OsuError::Response {
+                    body: String::from_utf8_lossy(bytes).into_owned(),
+                    error: ApiError { error },
+                    status: StatusCode::OK,
+                })
+            }
+        };
+
+        let content = STANDARD.decode(content).map_err(OsuError::ReplayDecoding)?;
+
+        Ok(Replay { content })
+    }
+}
+
+route_uri!(GetReplay<'_>);
+
+impl<'a> std::future::Future for GetReplay<'a> {
+    type Output = OsuResult<Replay>;
+
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        use std::task::Poll;
+
+        loop {
+            if let Some(fut) = self.as_mut().fut.as_mut() {
+                let bytes = match fut.as_mut().poll(cx) {
+                    Poll::Ready(Ok(bytes)) => bytes,
+                    Poll::Ready(Err(OsuError::NotFound)) => {
+                        return Poll::Ready(Err(OsuError::ReplayUnavailable))
+                    }
+                    Poll::Ready(Err(why)) => return Poll::Ready(Err(why)),
+                    Poll::Pending => return Poll::Pending,
+                };
+
+                return Poll::Ready(Self::decode(bytes.as_ref()));
+            } else {
+                self.as_mut().start();
+            }
+        }
+    }
+}
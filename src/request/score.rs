@@ -2,9 +2,11 @@ use super::{Pending, UserIdentification};
 use crate::{
     model::{GameMode, GameMods, Score},
     routing::Route,
-    Osu,
+    Osu, OsuError, OsuResult,
 };
 
+use std::collections::{hash_map::Entry, HashMap};
+
 /// Retrieve a [`Score`].
 pub struct GetScore<'a> {
     fut: Option<Pending<'a>>,
@@ -26,11 +28,14 @@ pub struct GetScores<'a> {
     map_id: u32,
     mode: Option<GameMode>,
     mods: Option<GameMods>,
+    mods_including: Option<GameMods>,
+    replays_only: bool,
+    require_nonempty: bool,
     user: Option<UserIdentification>,
 }
 
 macro_rules! impl_score {
-    ($name: ident, $default_limit: expr) => {
+    ($name: ident, $default_limit: expr $(, $field: ident: $value: expr)*) => {
         impl<'a> $name<'a> {
             pub(crate) fn new(osu: &'a Osu, map_id: u32) -> Self {
                 Self {
@@ -41,6 +46,7 @@ macro_rules! impl_score {
                     mode: None,
                     mods: None,
                     user: None,
+                    $($field: $value,)*
                 }
             }
 
@@ -53,10 +59,11 @@ macro_rules! impl_score {
             }
 
             /// Optional, amount of results from the top.
-            /// Range between 1 and 100, defaults to 50.
+            /// Range between 1 and 100, defaults to 50
+            /// or the client's default limit, see [`OsuBuilder`](crate::OsuBuilder).
             #[inline]
             pub fn limit(mut self, limit: u32) -> Self {
-                self.limit.replace(limit.max(1).min(100));
+                self.limit.replace(limit.clamp(1, GetScores::MAX_LIMIT));
 
                 self
             }
@@ -77,27 +84,149 @@ macro_rules! impl_score {
                 self
             }
 
-            fn start(&mut self) {
-                let route = Route::GetScore {
-                    limit: self.limit.take(),
+            /// Reset previously specified [`mods`](Self::mods), e.g. when reusing a builder.
+            #[inline]
+            pub fn clear_mods(mut self) -> Self {
+                self.mods.take();
+
+                self
+            }
+
+            fn route(&self) -> Route {
+                Route::GetScore {
+                    limit: self.limit.or(self.osu.unwrap().0.default_leaderboard_limit),
                     map_id: self.map_id,
-                    mode: self.mode.take(),
-                    mods: self.mods.take(),
-                    user: self.user.take(),
-                };
+                    mode: self.mode,
+                    mods: self.requested_mods(),
+                    user: self.user.clone(),
+                }
+            }
+
+            fn start(&mut self) {
+                let osu = self.osu.unwrap();
+                let route = self.route();
 
                 #[cfg(feature = "metrics")]
-                self.osu.unwrap().0.metrics.scores.inc();
+                osu.0.metrics.scores.inc();
 
-                self.fut
-                    .replace(Box::pin(self.osu.unwrap().request_bytes(route)));
+                self.fut.replace(Box::pin(osu.request_bytes(route)));
             }
         }
     };
 }
 
-impl_score!(GetScores, None);
-poll_vec_req!(GetScores<'_>, Score);
+impl_score!(
+    GetScores,
+    None,
+    mods_including: None,
+    replays_only: false,
+    require_nonempty: false
+);
+poll_vec_req!(GetScores<'_>, Score, post_process);
+route_uri!(GetScores<'_>);
+
+impl GetScores<'_> {
+    /// Maximum amount of scores that can be requested at once.
+    pub const MAX_LIMIT: u32 = 100;
+
+    /// Optional, only keep scores whose mods contain the given mods
+    /// e.g. `GameMods::Hidden` also keeps `HDHR` scores.
+    ///
+    /// Since the api only matches mods exactly, the leaderboard is requested
+    /// without mods and filtered afterwards. Hence, more data is transferred
+    /// and the filtered result may contain fewer scores than the specified limit.
+    /// Overrides [`GetScores::mods`].
+    #[inline]
+    pub fn mods_including(mut self, mods: GameMods) -> Self {
+        self.mods_including.replace(mods);
+
+        self
+    }
+
+    /// Optional, only keep scores whose replay is available.
+    ///
+    /// The filter is applied after the request so the result may contain
+    /// fewer scores than the specified limit.
+    #[inline]
+    pub fn with_replays_only(mut self) -> Self {
+        self.replays_only = true;
+
+        self
+    }
+
+    /// Optional, resolve to [`OsuError::NotFound`] instead of an empty vec
+    /// if no scores remain.
+    ///
+    /// Note that the api responds with an empty leaderboard for unknown map ids.
+    #[inline]
+    pub fn require_nonempty(mut self) -> Self {
+        self.require_nonempty = true;
+
+        self
+    }
+
+    /// Resolve to the highest score for each distinct mod combination of the leaderboard.
+    ///
+    /// Only the retrieved scores are considered, see [`limit`](GetScores::limit).
+    pub async fn best_per_mods(self) -> OsuResult<HashMap<GameMods, Score>> {
+        let mut best: HashMap<GameMods, Score> = HashMap::new();
+
+        for score in self.await? {
+            match best.entry(score.enabled_mods) {
+                Entry::Occupied(mut e) => {
+                    if e.get().score < score.score {
+                        e.insert(score);
+                    }
+                }
+                Entry::Vacant(e) => {
+                    e.insert(score);
+                }
+            }
+        }
+
+        Ok(best)
+    }
+
+    /// The leaderboard is requested without mods if it is filtered by `mods_including`.
+    fn requested_mods(&self) -> Option<GameMods> {
+        self.mods.filter(|_| self.mods_including.is_none())
+    }
+
+    fn post_process(&mut self, mut scores: Vec<Score>) -> OsuResult<Vec<Score>> {
+        for score in scores.iter_mut() {
+            score.mode = self.mode;
+        }
+
+        if let Some(mods) = self.mods_including {
+            scores.retain(|score| score.enabled_mods.contains(mods));
+        }
+
+        if self.replays_only {
+            scores.retain(|score| score.replay_available == Some(true));
+        }
+
+        if self.require_nonempty && scores.is_empty() {
+            return Err(OsuError::NotFound);
+        }
+
+        Ok(scores)
+    }
+}
 
 impl_score!(GetScore, Some(1));
-poll_req!(GetScore<'_>, Score);
+poll_req!(GetScore<'_>, Score, post_process);
+route_uri!(GetScore<'_>);
+
+impl GetScore<'_> {
+    fn requested_mods(&self) -> Option<GameMods> {
+        self.mods
+    }
+
+    fn post_process(&mut self, mut score: Option<Score>) -> OsuResult<Option<Score>> {
+        if let Some(score) = score.as_mut() {
+            score.mode = self.mode;
+        }
+
+        Ok(score)
+    }
+}
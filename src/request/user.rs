@@ -1,8 +1,14 @@
-use super::{Pending, UserIdentification};
+use super::{parse_single, Pending, UserIdentification};
 use crate::{
-    model::{GameMode, User},
+    model::{GameMode, User, UserBundle},
     routing::Route,
-    Osu,
+    Osu, OsuResult,
+};
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
 };
 
 /// Retrieve a [`User`]
@@ -15,6 +21,8 @@ pub struct GetUser<'a> {
     user: Option<UserIdentification>,
     mode: Option<GameMode>,
     event_days: Option<u32>,
+    without_events: bool,
+    fallback_ids: Vec<u32>,
 }
 
 impl<'a> GetUser<'a> {
@@ -26,6 +34,8 @@ impl<'a> GetUser<'a> {
             event_days: None,
             mode: None,
             user: Some(user.into()),
+            without_events: false,
+            fallback_ids: Vec::new(),
         }
     }
 
@@ -40,18 +50,84 @@ impl<'a> GetUser<'a> {
     /// Optional, must be between 1 and 31, defaults to 1
     #[inline]
     pub fn event_days(mut self, event_days: u32) -> Self {
-        self.event_days.replace(event_days.min(31).max(1));
+        self.event_days.replace(event_days.clamp(1, 31));
 
         self
     }
 
-    fn start(&mut self) {
-        let route = Route::GetUser {
-            user: self.user.take().unwrap(),
-            mode: self.mode.take(),
-            event_days: self.event_days.take(),
+    /// Optional, resolve to a user without events.
+    ///
+    /// The api always includes at least one day of events so this requests
+    /// the smallest window and clears the events afterwards.
+    /// The response still contains those events, hence the saved bandwidth
+    /// is limited to events older than a day.
+    #[inline]
+    pub fn without_events(mut self) -> Self {
+        self.without_events = true;
+
+        self.event_days(1)
+    }
+
+    /// Optional, user ids to try in order if the user could not be found.
+    ///
+    /// Useful if the user was specified by a name that might have changed.
+    /// Each fallback id requires an additional request.
+    #[inline]
+    pub fn or_ids(mut self, mut ids: Vec<u32>) -> Self {
+        ids.reverse();
+        self.fallback_ids = ids;
+
+        self
+    }
+
+    /// Resolve to the user together with their top and recent scores in the chosen mode.
+    ///
+    /// The scores are requested one after another, only if the user was found,
+    /// and identify the user by the retrieved id.
+    #[inline]
+    pub fn bundle(self) -> GetUserBundle<'a> {
+        GetUserBundle {
+            fut: None,
+            user: Some(self),
+            top_limit: None,
+            recent_limit: None,
+        }
+    }
+
+    /// Resolve to [`User::default`] instead of `Ok(None)` if the user was not found.
+    ///
+    /// A convenience for callers that prefer a placeholder user over handling `None`.
+    /// If the user was requested by id, the placeholder has that `user_id`.
+    ///
+    /// [`User::default`]: crate::model::User
+    pub async fn unwrap_or_default(self) -> OsuResult<User> {
+        let user_id = match self.user {
+            Some(UserIdentification::Id(user_id)) => Some(user_id),
+            _ => None,
         };
 
+        let mode = self.mode;
+
+        let user = self.await?.unwrap_or_else(|| User {
+            user_id: user_id.unwrap_or_default(),
+            mode,
+            ..Default::default()
+        });
+
+        Ok(user)
+    }
+
+    fn route(&self) -> Route {
+        Route::GetUser {
+            user: self.user.clone().unwrap(),
+            mode: self.mode,
+            event_days: self.event_days,
+        }
+    }
+
+    fn start(&mut self) {
+        let route = self.route();
+
         #[cfg(feature = "metrics")]
         self.osu.0.metrics.users.inc();
 
@@ -59,4 +135,126 @@ impl<'a> GetUser<'a> {
     }
 }
 
-poll_req!(GetUser<'_>, User);
+route_uri!(GetUser<'_>);
+
+impl Future for GetUser<'_> {
+    type Output = OsuResult<Option<User>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            if let Some(fut) = self.as_mut().fut.as_mut() {
+                let bytes = match fut.as_mut().poll(cx) {
+                    Poll::Ready(Ok(bytes)) => bytes,
+                    Poll::Ready(Err(why)) => return Poll::Ready(Err(why)),
+                    Poll::Pending => return Poll::Pending,
+                };
+
+                match parse_single::<User>(bytes.as_ref()) {
+                    Ok(None) => match self.fallback_ids.pop() {
+                        Some(id) => {
+                            self.user.replace(id.into());
+                            self.fut.take();
+                        }
+                        None => return Poll::Ready(Ok(None)),
+                    },
+                    Ok(Some(mut user)) => {
+                        if self.without_events {
+                            user.events.clear();
+                        }
+
+                        user.mode = self.mode;
+
+                        return Poll::Ready(Ok(Some(user)));
+                    }
+                    Err(why) => return Poll::Ready(Err(why)),
+                }
+            } else {
+                self.as_mut().start();
+            }
+        }
+    }
+}
+
+type PendingBundle<'a> = Pin<Box<dyn Future<Output = OsuResult<Option<UserBundle>>> + Send + 'a>>;
+
+/// Retrieve a [`UserBundle`] i.e. a [`User`] with their top and recent scores.
+///
+/// [`User`]: crate::model::User
+/// [`UserBundle`]: crate::model::UserBundle
+pub struct GetUserBundle<'a> {
+    fut: Option<PendingBundle<'a>>,
+    user: Option<GetUser<'a>>,
+
+    top_limit: Option<u32>,
+    recent_limit: Option<u32>,
+}
+
+impl<'a> GetUserBundle<'a> {
+    /// Optional, amount of top scores, defaults to 10 and is capped at 100.
+    #[inline]
+    pub fn top_limit(mut self, limit: u32) -> Self {
+        self.top_limit.replace(limit);
+
+        self
+    }
+
+    /// Optional, amount of recent scores, defaults to 10 and is capped at 50.
+    #[inline]
+    pub fn recent_limit(mut self, limit: u32) -> Self {
+        self.recent_limit.replace(limit);
+
+        self
+    }
+
+    fn start(&mut self) {
+        let user = self.user.take().unwrap();
+        let osu = user.osu;
+        let mode = user.mode;
+        let top_limit = self.top_limit;
+        let recent_limit = self.recent_limit;
+
+        let fut = async move {
+            let user = match user.await? {
+                Some(user) => user,
+                None => return Ok(None),
+            };
+
+            let mut top = osu.top_scores(user.user_id);
+            let mut recent = osu.recent_scores(user.user_id);
+
+            if let Some(mode) = mode {
+                top = top.mode(mode);
+                recent = recent.mode(mode);
+            }
+
+            if let Some(limit) = top_limit {
+                top = top.limit(limit);
+            }
+
+            if let Some(limit) = recent_limit {
+                recent = recent.limit(limit);
+            }
+
+            let top = top.await?;
+            let recent = recent.await?;
+
+            Ok(Some(UserBundle { user, top, recent }))
+        };
+
+        self.fut.replace(Box::pin(fut));
+    }
+}
+
+impl Future for GetUserBundle<'_> {
+    type Output = OsuResult<Option<UserBundle>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            if let Some(fut) = self.as_mut().fut.as_mut() {
+                return fut.as_mut().poll(cx);
+            } else {
+                self.as_mut().start();
+            }
+        }
+    }
+}
@@ -2,7 +2,7 @@ use super::{Pending, UserIdentification};
 use crate::{
     model::{GameMode, Score},
     routing::Route,
-    Osu,
+    Osu, OsuResult,
 };
 
 /// Retrieve the top scores of a [`User`].
@@ -30,8 +30,11 @@ pub struct GetUserRecent<'a> {
 }
 
 macro_rules! impl_user_score {
-    ($name: ident, $limit: literal, $metric: ident) => {
+    ($name: ident, $limit: literal, $default_limit: ident, $metric: ident) => {
         impl<'a> $name<'a> {
+            /// Maximum amount of scores that can be requested at once.
+            pub const MAX_LIMIT: u32 = $limit;
+
             pub(crate) fn new(osu: &'a Osu, user: impl Into<UserIdentification>) -> Self {
                 Self {
                     fut: None,
@@ -42,7 +45,8 @@ macro_rules! impl_user_score {
                 }
             }
 
-            /// Optional, amount of results, defaults to 10.
+            /// Optional, amount of results, defaults to 10
+            /// or the client's default limit, see [`OsuBuilder`](crate::OsuBuilder).
             ///
             /// # Upper limit
             ///
@@ -50,7 +54,7 @@ macro_rules! impl_user_score {
             ///   - `GetUserRecent`: 50
             #[inline]
             pub fn limit(mut self, limit: u32) -> Self {
-                self.limit.replace(limit.max(0).min($limit));
+                self.limit.replace(limit.min(Self::MAX_LIMIT));
 
                 self
             }
@@ -63,24 +67,52 @@ macro_rules! impl_user_score {
                 self
             }
 
+            fn route(&self) -> Route {
+                Route::$name {
+                    limit: self.limit.or(self.osu.0.$default_limit),
+                    mode: self.mode,
+                    user: self.user.clone().unwrap(),
+                }
+            }
+
             fn start(&mut self) {
-                let route = Route::$name {
-                    limit: self.limit.take(),
-                    mode: self.mode.take(),
-                    user: self.user.take().unwrap(),
-                };
+                let route = self.route();
 
                 #[cfg(feature = "metrics")]
                 self.osu.0.metrics.$metric.inc();
 
                 self.fut.replace(Box::pin(self.osu.request_bytes(route)));
             }
+
+            fn post_process(&mut self, mut scores: Vec<Score>) -> OsuResult<Vec<Score>> {
+                for score in scores.iter_mut() {
+                    score.mode = self.mode;
+                }
+
+                Ok(scores)
+            }
         }
     };
 }
 
-impl_user_score!(GetUserBest, 100, top_scores);
-poll_vec_req!(GetUserBest<'_>, Score);
+impl_user_score!(GetUserBest, 100, default_top_limit, top_scores);
+poll_vec_req!(GetUserBest<'_>, Score, post_process);
+route_uri!(GetUserBest<'_>);
+
+impl GetUserBest<'_> {
+    /// Resolve to the top scores, each paired with its 1-based position in the user's top list.
+    pub async fn with_positions(self) -> OsuResult<Vec<(usize, Score)>> {
+        self.await.map(|scores| (1..).zip(scores).collect())
+    }
+}
+
+impl_user_score!(GetUserRecent, 50, default_recent_limit, recent_scores);
+poll_vec_req!(GetUserRecent<'_>, Score, post_process);
+route_uri!(GetUserRecent<'_>);
 
-impl_user_score!(GetUserRecent, 50, recent_scores);
-poll_vec_req!(GetUserRecent<'_>, Score);
+impl GetUserRecent<'_> {
+    /// Resolve to only the most recent score, or `None` if the user has no recent scores.
+    pub async fn latest(self) -> OsuResult<Option<Score>> {
+        self.limit(1).await.map(|mut scores| scores.pop())
+    }
+}
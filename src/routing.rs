@@ -54,6 +54,20 @@ pub(crate) enum Route {
     },
     /// Route information to get a multiplayer match
     GetMatch { match_id: u32 },
+    /// Route information to get the replay of a score
+    GetReplay {
+        /// The map on which the score was set
+        map_id: u32,
+
+        /// The mode of the score
+        mode: Option<GameMode>,
+
+        /// GameMods of the score
+        mods: Option<GameMods>,
+
+        /// The user who set the score
+        user: UserIdentification,
+    },
     /// Route information to get scores
     GetScore {
         /// Upper limit of beatmaps to retrieve
@@ -106,6 +120,23 @@ pub(crate) enum Route {
     },
 }
 
+#[cfg(feature = "mem-cache")]
+impl Route {
+    /// Whether responses of the route may be cached.
+    pub(crate) fn is_cacheable(&self) -> bool {
+        matches!(self, Route::GetBeatmaps { .. } | Route::GetUser { .. })
+    }
+}
+
+/// Append the mode unless it is `GameMode::Osu`, the api's default for scores, users, and replays.
+///
+/// Not applicable for beatmaps since those are not filtered by mode if none is specified.
+fn write_mode(uri: &mut String, mode: Option<GameMode>) {
+    if let Some(mode) = mode.filter(|&mode| mode != GameMode::Osu) {
+        let _ = write!(uri, "&{}={}", MODE_TAG, mode as u8);
+    }
+}
+
 impl From<Route> for Request {
     fn from(route: Route) -> Self {
         let uri = match route {
@@ -147,7 +178,7 @@ impl From<Route> for Request {
                 }
 
                 if let Some(mods) = mods {
-                    let _ = write!(uri, "&{}={}", MODS_TAG, mods.bits());
+                    let _ = write!(uri, "&{}={}", MODS_TAG, mods.to_api_value());
                 }
 
                 if let Some(Ok(date)) = since.map(|date| date.format(NAIVE_DATETIME_FORMAT)) {
@@ -161,6 +192,22 @@ impl From<Route> for Request {
                 uri
             }
             Route::GetMatch { match_id } => format!("get_match?{}={}", MP_TAG, match_id),
+            Route::GetReplay {
+                map_id,
+                mode,
+                mods,
+                user,
+            } => {
+                let mut uri = format!("get_replay?{}={}&{}", MAP_TAG, map_id, user);
+
+                write_mode(&mut uri, mode);
+
+                if let Some(mods) = mods {
+                    let _ = write!(uri, "&{}={}", MODS_TAG, mods.to_api_value());
+                }
+
+                uri
+            }
             Route::GetScore {
                 limit,
                 map_id,
@@ -174,12 +221,10 @@ impl From<Route> for Request {
                     let _ = write!(uri, "&{}={}", LIMIT_TAG, limit);
                 }
 
-                if let Some(mode) = mode {
-                    let _ = write!(uri, "&{}={}", MODE_TAG, mode as u8);
-                }
+                write_mode(&mut uri, mode);
 
                 if let Some(mods) = mods {
-                    let _ = write!(uri, "&{}={}", MODS_TAG, mods.bits());
+                    let _ = write!(uri, "&{}={}", MODS_TAG, mods.to_api_value());
                 }
 
                 if let Some(user) = user {
@@ -195,9 +240,7 @@ impl From<Route> for Request {
             } => {
                 let mut uri = format!("get_user?{}", user);
 
-                if let Some(mode) = mode {
-                    let _ = write!(uri, "&{}={}", MODE_TAG, mode as u8);
-                }
+                write_mode(&mut uri, mode);
 
                 if let Some(days) = event_days {
                     let _ = write!(uri, "&{}={}", EVENT_DAYS_TAG, days);
@@ -212,9 +255,7 @@ impl From<Route> for Request {
                     let _ = write!(uri, "&{}={}", LIMIT_TAG, limit);
                 }
 
-                if let Some(mode) = mode {
-                    let _ = write!(uri, "&{}={}", MODE_TAG, mode as u8);
-                }
+                write_mode(&mut uri, mode);
 
                 uri
             }
@@ -225,9 +266,7 @@ impl From<Route> for Request {
                     let _ = write!(uri, "&{}={}", LIMIT_TAG, limit);
                 }
 
-                if let Some(mode) = mode {
-                    let _ = write!(uri, "&{}={}", MODE_TAG, mode as u8);
-                }
+                write_mode(&mut uri, mode);
 
                 uri
             }
@@ -236,3 +275,211 @@ impl From<Route> for Request {
         Request(uri.into_boxed_str())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Route;
+    use crate::{
+        model::{GameMode, GameMods},
+        request::{Request, UserIdentification},
+        Osu,
+    };
+    use time::OffsetDateTime;
+
+    fn uri(route: Route) -> String {
+        Request::from(route).uri().to_owned()
+    }
+
+    #[test]
+    fn route_get_beatmaps() {
+        let route = Route::GetBeatmaps {
+            creator: None,
+            hash: None,
+            limit: None,
+            map_id: None,
+            mapset_id: None,
+            mode: None,
+            mods: None,
+            since: None,
+            with_converted: None,
+        };
+        assert_eq!(uri(route), "get_beatmaps?");
+
+        let route = Route::GetBeatmaps {
+            creator: Some(UserIdentification::Name("Mao".to_owned())),
+            hash: Some("abc".into()),
+            limit: Some(50),
+            map_id: Some(2),
+            mapset_id: Some(1),
+            mode: Some(GameMode::Taiko),
+            mods: Some(GameMods::Hidden | GameMods::HardRock),
+            since: Some(OffsetDateTime::from_unix_timestamp(1_580_674_800).unwrap()),
+            with_converted: Some(true),
+        };
+        assert_eq!(
+            uri(route),
+            "get_beatmaps?&type=string&u=Mao&h=abc&limit=50&b=2&s=1&m=1&mods=24\
+            &since=2020-02-02 20:20:00&a=1"
+        );
+    }
+
+    #[test]
+    fn route_get_match() {
+        let route = Route::GetMatch { match_id: 58494587 };
+        assert_eq!(uri(route), "get_match?mp=58494587");
+    }
+
+    #[test]
+    fn route_get_replay() {
+        let route = Route::GetReplay {
+            map_id: 905576,
+            mode: None,
+            mods: None,
+            user: UserIdentification::Id(2),
+        };
+        assert_eq!(uri(route), "get_replay?b=905576&type=id&u=2");
+
+        let route = Route::GetReplay {
+            map_id: 905576,
+            mode: Some(GameMode::Taiko),
+            mods: Some(GameMods::Hidden | GameMods::DoubleTime),
+            user: UserIdentification::Name("Badewanne3".to_owned()),
+        };
+        assert_eq!(
+            uri(route),
+            "get_replay?b=905576&type=string&u=Badewanne3&m=1&mods=72"
+        );
+    }
+
+    #[test]
+    fn route_get_score() {
+        let route = Route::GetScore {
+            limit: None,
+            map_id: 905576,
+            mode: None,
+            mods: None,
+            user: None,
+        };
+        assert_eq!(uri(route), "get_scores?b=905576");
+
+        let route = Route::GetScore {
+            limit: Some(100),
+            map_id: 905576,
+            mode: Some(GameMode::Mania),
+            mods: Some(GameMods::NightCore),
+            user: Some(UserIdentification::Id(2)),
+        };
+        assert_eq!(
+            uri(route),
+            "get_scores?b=905576&limit=100&m=3&mods=576&type=id&u=2"
+        );
+    }
+
+    #[test]
+    fn route_get_user() {
+        let route = Route::GetUser {
+            user: UserIdentification::Id(2),
+            mode: Some(GameMode::Catch),
+            event_days: Some(31),
+        };
+        assert_eq!(uri(route), "get_user?type=id&u=2&m=2&event_days=31");
+    }
+
+    #[test]
+    fn route_get_user_best() {
+        let route = Route::GetUserBest {
+            limit: Some(100),
+            mode: Some(GameMode::Osu),
+            user: UserIdentification::Name("Badewanne3".to_owned()),
+        };
+        assert_eq!(
+            uri(route),
+            "get_user_best?type=string&u=Badewanne3&limit=100"
+        );
+    }
+
+    #[test]
+    fn route_omits_default_mode() {
+        let route = Route::GetUser {
+            user: UserIdentification::Id(2),
+            mode: Some(GameMode::Osu),
+            event_days: None,
+        };
+        assert!(!uri(route).contains("&m="));
+
+        let route = Route::GetScore {
+            limit: None,
+            map_id: 905576,
+            mode: Some(GameMode::Osu),
+            mods: None,
+            user: None,
+        };
+        assert_eq!(uri(route), "get_scores?b=905576");
+
+        let route = Route::GetUserRecent {
+            limit: None,
+            mode: Some(GameMode::Osu),
+            user: UserIdentification::Id(2),
+        };
+        assert!(!uri(route).contains("&m="));
+
+        // Beatmaps of all modes are retrieved if no mode is specified
+        let route = Route::GetBeatmaps {
+            creator: None,
+            hash: None,
+            limit: None,
+            map_id: None,
+            mapset_id: None,
+            mode: Some(GameMode::Osu),
+            mods: None,
+            since: None,
+            with_converted: None,
+        };
+        assert_eq!(uri(route), "get_beatmaps?&m=0");
+    }
+
+    #[test]
+    fn route_get_user_recent() {
+        let route = Route::GetUserRecent {
+            limit: None,
+            mode: None,
+            user: UserIdentification::Id(2),
+        };
+        assert_eq!(uri(route), "get_user_recent?type=id&u=2");
+    }
+
+    #[test]
+    fn beatmap_as_converted() {
+        let osu = Osu::new("api_key");
+
+        let Request(uri) = osu
+            .beatmap()
+            .map_id(1)
+            .as_converted(GameMode::Mania)
+            .route()
+            .into();
+        assert_eq!(uri.as_ref(), "get_beatmaps?&limit=1&b=1&m=3&a=1");
+
+        let Request(uri) = osu
+            .beatmap()
+            .map_id(1)
+            .as_converted(GameMode::Osu)
+            .route()
+            .into();
+        assert_eq!(uri.as_ref(), "get_beatmaps?&limit=1&b=1&m=0");
+    }
+
+    #[test]
+    fn beatmap_clear_mods() {
+        let osu = Osu::new("api_key");
+
+        let Request(uri) = osu
+            .beatmap()
+            .map_id(1)
+            .mods(GameMods::DoubleTime)
+            .clear_mods()
+            .route()
+            .into();
+        assert_eq!(uri.as_ref(), "get_beatmaps?&limit=1&b=1");
+    }
+}
@@ -5,11 +5,18 @@ use serde::{
 };
 use std::{
     convert::TryFrom,
-    fmt::{Formatter, Result as FmtResult},
+    fmt::{Display, Formatter, Result as FmtResult},
 };
 
 struct ApprovalStatusVisitor;
 
+/// Statuses introduced by osu! after this version should not fail the whole parse.
+fn unknown_status(value: impl Display) -> ApprovalStatus {
+    warn!("Unknown approval status `{}`, defaulting to Pending", value);
+
+    ApprovalStatus::Pending
+}
+
 impl<'de> Visitor<'de> for ApprovalStatusVisitor {
     type Value = ApprovalStatus;
 
@@ -26,6 +33,7 @@ impl<'de> Visitor<'de> for ApprovalStatusVisitor {
             "0" | "pending" => Ok(ApprovalStatus::Pending),
             "-1" | "wip" => Ok(ApprovalStatus::WIP),
             "-2" | "graveyard" => Ok(ApprovalStatus::Graveyard),
+            _ if v.parse::<i64>().is_ok() => Ok(unknown_status(v)),
             _ => Err(Error::invalid_value(
                 Unexpected::Str(v),
                 &r#"
@@ -42,13 +50,19 @@ impl<'de> Visitor<'de> for ApprovalStatusVisitor {
     }
 
     fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
-        ApprovalStatus::try_from(v as i8)
-            .map_err(|_| Error::invalid_value(Unexpected::Signed(v), &"value between -2 and 4"))
+        let status = i8::try_from(v)
+            .ok()
+            .and_then(|v| ApprovalStatus::try_from(v).ok());
+
+        Ok(status.unwrap_or_else(|| unknown_status(v)))
     }
 
     fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
-        ApprovalStatus::try_from(v as i8)
-            .map_err(|_| Error::invalid_value(Unexpected::Unsigned(v), &"value between -2 and 4"))
+        let status = i8::try_from(v)
+            .ok()
+            .and_then(|v| ApprovalStatus::try_from(v).ok());
+
+        Ok(status.unwrap_or_else(|| unknown_status(v)))
     }
 }
 
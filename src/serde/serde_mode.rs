@@ -15,20 +15,12 @@ impl<'de> Visitor<'de> for ModeVisitor {
     }
 
     fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
-        let mode = match v {
-            "0" | "osu" | "osu!" => GameMode::Osu,
-            "1" | "taiko" | "tko" => GameMode::Taiko,
-            "2" | "ctb" | "fruits" | "catch" => GameMode::Catch,
-            "3" | "mania" | "mna" => GameMode::Mania,
-            _ => {
-                return Err(Error::invalid_value(
-                    Unexpected::Str(v),
-                    &r#""0", "osu", "1", "taiko", "tko", "2", 
-                    "ctb", "fruits", "catch", "3", "mania", or "mna""#,
-                ))
-            }
-        };
-        Ok(mode)
+        v.parse().map_err(|_| {
+            Error::invalid_value(
+                Unexpected::Str(v),
+                &"a mode name or number, see `GameMode::from_str`",
+            )
+        })
     }
 
     fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
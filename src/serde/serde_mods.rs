@@ -4,12 +4,24 @@ use serde::{
     Deserialize, Deserializer, Serialize, Serializer,
 };
 use std::{
+    convert::TryFrom,
     fmt::{Formatter, Result as FmtResult},
     str::FromStr,
 };
 
 struct ModsVisitor;
 
+/// Keep the recognized mods instead of discarding all of them because of unknown bits.
+fn known_mods(value: u32) -> GameMods {
+    let (mods, unknown) = GameMods::from_bits_partial(value);
+
+    if unknown != 0 {
+        debug!("Ignoring unknown mod bits {} of {}", unknown, value);
+    }
+
+    mods
+}
+
 impl<'de> Visitor<'de> for ModsVisitor {
     type Value = Option<GameMods>;
 
@@ -19,7 +31,7 @@ impl<'de> Visitor<'de> for ModsVisitor {
 
     fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
         match u32::from_str(v) {
-            Ok(n) => Ok(GameMods::from_bits(n)),
+            Ok(n) => Ok(Some(known_mods(n))),
             Err(_) => GameMods::from_str(v)
                 .map(Some)
                 .map_err(|_| Error::invalid_value(Unexpected::Str(v), &"GameMods")),
@@ -27,7 +39,13 @@ impl<'de> Visitor<'de> for ModsVisitor {
     }
 
     fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
-        Ok(GameMods::from_bits(v as u32))
+        Ok(Some(known_mods(v as u32)))
+    }
+
+    fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+        u32::try_from(v)
+            .map(|n| Some(known_mods(n)))
+            .map_err(|_| Error::invalid_value(Unexpected::Signed(v), &"GameMods"))
     }
 
     fn visit_some<D: Deserializer<'de>>(self, d: D) -> Result<Self::Value, D::Error> {
@@ -38,6 +56,11 @@ impl<'de> Visitor<'de> for ModsVisitor {
     fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
         Ok(None)
     }
+
+    #[inline]
+    fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
 }
 
 pub(crate) fn to_maybe_mods<'de, D: Deserializer<'de>>(d: D) -> Result<Option<GameMods>, D::Error> {
@@ -52,6 +75,6 @@ impl<'de> Deserialize<'de> for GameMods {
 
 impl Serialize for GameMods {
     fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
-        s.serialize_u32(self.bits())
+        s.serialize_u32(self.to_api_value())
     }
 }
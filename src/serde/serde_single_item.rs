@@ -5,11 +5,19 @@ use std::{
 
 use serde::de::{Deserialize, IgnoredAny, SeqAccess, Visitor};
 
-#[derive(Default)]
 pub(crate) struct SingleItemVisitor<T> {
     phantom: PhantomData<T>,
 }
 
+impl<T> Default for SingleItemVisitor<T> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            phantom: PhantomData,
+        }
+    }
+}
+
 impl<'de, T: Deserialize<'de>> Visitor<'de> for SingleItemVisitor<T> {
     type Value = Option<T>;
 
@@ -0,0 +1,1625 @@
+extern crate rosu;
+
+use rosu::{model::*, request::UserIdentification, Osu, OsuError};
+use serde_json::{json, Value};
+use wiremock::{
+    matchers::{method, path, query_param, query_param_is_missing},
+    Mock, MockServer, ResponseTemplate,
+};
+
+fn make_osu(server: &MockServer) -> Osu {
+    Osu::builder("api_key")
+        .base_url(format!("{}/", server.uri()))
+        .build()
+        .unwrap()
+}
+
+fn user_json(user_id: u32, username: &str, pp_raw: f32) -> Value {
+    json!({
+        "user_id": user_id.to_string(),
+        "username": username,
+        "join_date": "2019-01-03 07:01:28",
+        "count300": "1000",
+        "count100": "100",
+        "count50": "10",
+        "playcount": "50",
+        "ranked_score": "123456789",
+        "total_score": "234567890",
+        "pp_rank": "4321",
+        "level": "99.5",
+        "pp_raw": pp_raw.to_string(),
+        "accuracy": "98.76",
+        "count_rank_ss": "1",
+        "count_rank_ssh": "2",
+        "count_rank_s": "3",
+        "count_rank_sh": "4",
+        "count_rank_a": "5",
+        "country": "DE",
+        "total_seconds_played": "3600",
+        "pp_country_rank": "123",
+        "events": []
+    })
+}
+
+fn score_json(user_id: u32, score: u32, mods: GameMods) -> Value {
+    json!({
+        "score_id": (user_id as u64 * 1000).to_string(),
+        "score": score.to_string(),
+        "username": format!("user{}", user_id),
+        "count300": "500",
+        "count100": "20",
+        "count50": "2",
+        "countmiss": "1",
+        "maxcombo": "700",
+        "countkatu": "10",
+        "countgeki": "100",
+        "perfect": "0",
+        "enabled_mods": mods.bits().to_string(),
+        "user_id": user_id.to_string(),
+        "date": "2020-05-01 12:34:56",
+        "rank": "A",
+        "pp": "123.45",
+        "replay_available": "1"
+    })
+}
+
+fn map_json(map_id: u32, mapset_id: u32, version: &str, stars: f32) -> Value {
+    json!({
+        "beatmapset_id": mapset_id.to_string(),
+        "beatmap_id": map_id.to_string(),
+        "approved": "1",
+        "total_length": "167",
+        "hit_length": "164",
+        "version": version,
+        "file_md5": "a5b4d2c1e3f6a7b8c9d0e1f2a3b4c5d6",
+        "diff_size": "4",
+        "diff_overall": "8",
+        "diff_approach": "9",
+        "diff_drain": "5",
+        "mode": "0",
+        "count_normal": "464",
+        "count_slider": "229",
+        "count_spinner": "1",
+        "submit_date": "2019-12-23 11:01:42",
+        "approved_date": "2020-02-06 16:40:23",
+        "last_update": "2020-01-29 12:51:02",
+        "artist": "Artist",
+        "title": "Title",
+        "creator": "Mao",
+        "creator_id": "3178418",
+        "bpm": "174",
+        "source": "",
+        "tags": "tag1 tag2",
+        "genre_id": "2",
+        "language_id": "3",
+        "favourite_count": "84",
+        "rating": "9.26",
+        "storyboard": "0",
+        "video": "0",
+        "download_unavailable": "0",
+        "audio_unavailable": "0",
+        "playcount": "98760",
+        "passcount": "10983",
+        "max_combo": "1063",
+        "diff_aim": "2.6457",
+        "diff_speed": "2.3561",
+        "difficultyrating": stars.to_string()
+    })
+}
+
+fn game_json(game_id: u32, user_scores: &[(u32, u32)]) -> Value {
+    let scores: Vec<_> = user_scores
+        .iter()
+        .enumerate()
+        .map(|(slot, (user_id, score))| {
+            json!({
+                "slot": slot.to_string(),
+                "team": "0",
+                "user_id": user_id.to_string(),
+                "score": score.to_string(),
+                "maxcombo": "500",
+                "rank": "0",
+                "count50": "1",
+                "count100": "10",
+                "count300": "400",
+                "countmiss": "2",
+                "countgeki": "50",
+                "countkatu": "5",
+                "perfect": "0",
+                "pass": "1",
+                "enabled_mods": null
+            })
+        })
+        .collect();
+
+    json!({
+        "game_id": game_id.to_string(),
+        "start_time": format!("2020-02-02 20:{:02}:00", game_id % 60),
+        "end_time": format!("2020-02-02 20:{:02}:30", game_id % 60),
+        "beatmap_id": "905576",
+        "play_mode": "0",
+        "match_type": "0",
+        "scoring_type": "3",
+        "team_type": "0",
+        "mods": "0",
+        "scores": scores
+    })
+}
+
+fn match_json(match_id: u32, games: Vec<Value>, ended: bool) -> Value {
+    json!({
+        "match": {
+            "match_id": match_id.to_string(),
+            "name": "OWC: (Germany) vs (Japan)",
+            "start_time": "2020-02-02 20:00:00",
+            "end_time": if ended { json!("2020-02-02 21:00:00") } else { Value::Null }
+        },
+        "games": games
+    })
+}
+
+#[tokio::test]
+async fn user_multi_mode() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user"))
+        .and(query_param_is_missing("m"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([user_json(
+            2,
+            "Badewanne3",
+            1234.5
+        )])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user"))
+        .and(query_param("m", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user"))
+        .and(query_param("m", "3"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([user_json(
+            2,
+            "Badewanne3",
+            4321.5
+        )])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+    let modes = [GameMode::Mania, GameMode::Taiko, GameMode::Osu];
+    let users = osu.user_multi_mode("Badewanne3", &modes).await.unwrap();
+
+    assert_eq!(users.len(), 2);
+    assert_eq!(users[0].0, GameMode::Mania);
+    assert_eq!(users[0].1.mode, Some(GameMode::Mania));
+    assert!((users[0].1.pp_raw - 4321.5).abs() < f32::EPSILON);
+    assert_eq!(users[1].0, GameMode::Osu);
+    assert!((users[1].1.pp_raw - 1234.5).abs() < f32::EPSILON);
+}
+
+#[tokio::test]
+async fn user_if_ranked() {
+    let server = MockServer::start().await;
+
+    let mut inactive = user_json(2, "inactive", 0.0);
+    inactive["pp_rank"] = json!("0");
+
+    for (user_id, body) in [
+        ("1", json!([user_json(1, "ranked", 1234.5)])),
+        ("2", json!([inactive])),
+    ] {
+        Mock::given(method("GET"))
+            .and(path("/get_user"))
+            .and(query_param("u", user_id))
+            .and(query_param("m", "3"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let osu = make_osu(&server);
+
+    let ranked = osu.user_if_ranked(1, GameMode::Mania).await.unwrap();
+    assert_eq!(ranked.map(|user| user.user_id), Some(1));
+
+    let unranked = osu.user_if_ranked(2, GameMode::Mania).await.unwrap();
+    assert!(unranked.is_none());
+}
+
+#[tokio::test]
+async fn recent_map_frequency() {
+    let server = MockServer::start().await;
+
+    let scores: Vec<_> = [905576, 2271405, 905576, 1, 905576, 2271405]
+        .iter()
+        .map(|map_id| {
+            let mut score = score_json(2, 1_000_000, GameMods::NoMod);
+            score["beatmap_id"] = json!(map_id.to_string());
+
+            score
+        })
+        .collect();
+
+    Mock::given(method("GET"))
+        .and(path("/get_user_recent"))
+        .and(query_param("u", "2"))
+        .and(query_param("m", "3"))
+        .and(query_param("limit", "50"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(scores))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+    let frequency = osu
+        .recent_map_frequency(2, GameMode::Mania, 50)
+        .await
+        .unwrap();
+
+    assert_eq!(frequency, vec![(905576, 3), (2271405, 2), (1, 1)]);
+}
+
+#[tokio::test]
+async fn users_sorted_by_pp() {
+    let server = MockServer::start().await;
+
+    for (user_id, body) in [
+        ("1", json!([user_json(1, "first", 100.0)])),
+        ("2", json!([user_json(2, "second", 300.0)])),
+        ("3", json!([user_json(3, "third", 100.0)])),
+        ("4", json!([])),
+    ] {
+        Mock::given(method("GET"))
+            .and(path("/get_user"))
+            .and(query_param("u", user_id))
+            .and(query_param("m", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let osu = make_osu(&server);
+    let users = (1..=4).map(UserIdentification::from).collect();
+    let users = osu
+        .users_sorted_by_pp(users, GameMode::Taiko)
+        .await
+        .unwrap();
+
+    let user_ids: Vec<_> = users.iter().map(|user| user.user_id).collect();
+    assert_eq!(user_ids, vec![2, 1, 3]);
+}
+
+#[tokio::test]
+async fn score_get_user_mode() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user_recent"))
+        .and(query_param("m", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([score_json(
+            2,
+            500_000,
+            GameMods::NoMod
+        )])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user"))
+        .and(query_param("u", "2"))
+        .and(query_param("m", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([user_json(
+            2,
+            "Badewanne3",
+            1234.5
+        )])))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+    let score = osu
+        .recent_scores(2)
+        .mode(GameMode::Taiko)
+        .latest()
+        .await
+        .unwrap()
+        .unwrap();
+
+    let user = score.get_user(&osu).await.unwrap().unwrap();
+    assert_eq!(user.mode, Some(GameMode::Taiko));
+
+    let mut untagged = score.clone();
+    untagged.mode = None;
+    let user = untagged.get_user_mode(&osu, GameMode::Taiko).await.unwrap();
+    assert_eq!(user.unwrap().mode, Some(GameMode::Taiko));
+}
+
+#[tokio::test]
+async fn user_unwrap_or_default() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+
+    let user = osu
+        .user(2)
+        .mode(GameMode::Mania)
+        .unwrap_or_default()
+        .await
+        .unwrap();
+    assert_eq!(user.user_id, 2);
+    assert_eq!(user.mode, Some(GameMode::Mania));
+    assert!(user.username.is_empty());
+
+    let user = osu.user("unknown").unwrap_or_default().await.unwrap();
+    assert_eq!(user.user_id, 0);
+}
+
+#[tokio::test]
+async fn rank_within() {
+    let server = MockServer::start().await;
+
+    for (user_id, body) in [
+        ("1", json!([user_json(1, "first", 200.0)])),
+        ("2", json!([user_json(2, "second", 300.0)])),
+        ("3", json!([user_json(3, "third", 100.0)])),
+        ("4", json!([])),
+    ] {
+        Mock::given(method("GET"))
+            .and(path("/get_user"))
+            .and(query_param("u", user_id))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(&server)
+            .await;
+    }
+
+    let osu = make_osu(&server);
+
+    let rank = osu.rank_within(1, &[2, 3], GameMode::Osu).await.unwrap();
+    assert_eq!(rank, Some(2));
+
+    let rank = osu.rank_within(2, &[1, 2, 3], GameMode::Osu).await.unwrap();
+    assert_eq!(rank, Some(1));
+
+    let rank = osu.rank_within(4, &[1, 2, 3], GameMode::Osu).await.unwrap();
+    assert_eq!(rank, None);
+}
+
+#[cfg(feature = "mem-cache")]
+#[tokio::test]
+async fn cached_user() {
+    use std::time::Duration;
+
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([user_json(
+            2,
+            "Badewanne3",
+            1234.5
+        )])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let osu = Osu::builder("api_key")
+        .base_url(format!("{}/", server.uri()))
+        .cache_ttl(Duration::from_secs(60))
+        .build()
+        .unwrap();
+
+    let first = osu.user(2).await.unwrap().unwrap();
+    let second = osu.user(2).await.unwrap().unwrap();
+    assert_eq!(first.user_id, second.user_id);
+}
+
+#[cfg(feature = "record")]
+#[tokio::test]
+async fn replay_fixture() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let osu = Osu::builder("api_key")
+        .base_url(format!("{}/", server.uri()))
+        .fixture_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures"))
+        .build()
+        .unwrap();
+
+    let user = osu.user(2).await.unwrap().unwrap();
+    assert_eq!(user.username, "peppy");
+    assert_eq!(user.country, "AU");
+}
+
+#[cfg(feature = "record")]
+#[tokio::test]
+async fn record_fixture() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([user_json(
+            3,
+            "Badewanne3",
+            1234.5
+        )])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let dir = std::env::temp_dir().join(format!("rosu-fixtures-{}", std::process::id()));
+
+    let build = || {
+        Osu::builder("api_key")
+            .base_url(format!("{}/", server.uri()))
+            .fixture_dir(&dir)
+            .build()
+            .unwrap()
+    };
+
+    let recorded = build().user(3).await.unwrap().unwrap();
+    let replayed = build().user(3).await.unwrap().unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert_eq!(recorded, replayed);
+    assert_eq!(replayed.username, "Badewanne3");
+}
+
+#[cfg(feature = "metrics")]
+#[tokio::test]
+async fn metrics_count_started_requests() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+    let users = || osu.metrics().with_label_values(&["Users", "rosu"]).get();
+
+    let fut = osu.user(2);
+    drop(fut);
+    assert_eq!(users(), 0);
+
+    osu.user(2).await.unwrap();
+    assert_eq!(users(), 1);
+}
+
+#[tokio::test]
+async fn client_name() {
+    use wiremock::matchers::header_regex;
+
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user"))
+        .and(header_regex("User-Agent", r"\) rosu my-bot$"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let osu = Osu::builder("api_key")
+        .base_url(format!("{}/", server.uri()))
+        .client_name("my-bot")
+        .build()
+        .unwrap();
+
+    osu.user(2).await.unwrap();
+
+    #[cfg(feature = "metrics")]
+    {
+        use prometheus::core::Collector;
+
+        let families = osu.metrics().collect();
+        let users = families[0]
+            .get_metric()
+            .iter()
+            .find(|metric| {
+                metric
+                    .get_label()
+                    .iter()
+                    .any(|label| label.get_name() == "type" && label.get_value() == "Users")
+            })
+            .unwrap();
+
+        let client = users
+            .get_label()
+            .iter()
+            .find(|label| label.get_name() == "client")
+            .unwrap();
+        assert_eq!(client.get_value(), "my-bot");
+        assert_eq!(users.get_counter().get_value() as u32, 1);
+    }
+}
+
+#[tokio::test]
+async fn default_headers() {
+    use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
+    use wiremock::matchers::{header, header_regex};
+
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user"))
+        .and(header("CF-Access-Client-Id", "client-id"))
+        .and(header_regex("User-Agent", r"\) rosu$"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut headers = HeaderMap::new();
+    headers.insert("CF-Access-Client-Id", HeaderValue::from_static("client-id"));
+    headers.insert(USER_AGENT, HeaderValue::from_static("overridden"));
+
+    let osu = Osu::builder("api_key")
+        .base_url(format!("{}/", server.uri()))
+        .default_headers(headers)
+        .build()
+        .unwrap();
+
+    osu.user(2).await.unwrap();
+}
+
+#[tokio::test]
+async fn disabled_ratelimit() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(50)
+        .mount(&server)
+        .await;
+
+    let osu = Osu::builder("api_key")
+        .base_url(format!("{}/", server.uri()))
+        .disable_ratelimit()
+        .build()
+        .unwrap();
+
+    let start = std::time::Instant::now();
+
+    for user_id in 0..50 {
+        osu.user(user_id).await.unwrap();
+    }
+
+    // With the default limit of 15 requests per second this would take over 3 seconds
+    assert!(start.elapsed() < std::time::Duration::from_secs(1));
+}
+
+#[cfg(feature = "tracing")]
+#[tokio::test]
+async fn tracing_span() {
+    use std::{
+        fmt::Debug,
+        sync::{Arc, Mutex},
+    };
+    use tracing::{
+        field::{Field, Visit},
+        span::{Attributes, Id, Record},
+        Event, Metadata, Subscriber,
+    };
+
+    type RecordedSpans = Vec<(&'static str, Vec<String>)>;
+
+    /// Keeps the name and the recorded fields of every span.
+    #[derive(Clone, Default)]
+    struct SpanRecorder(Arc<Mutex<RecordedSpans>>);
+
+    struct FieldVisitor<'f>(&'f mut Vec<String>);
+
+    impl Visit for FieldVisitor<'_> {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.push(format!("{}={}", field.name(), value));
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0.push(format!("{}={:?}", field.name(), value));
+        }
+    }
+
+    impl Subscriber for SpanRecorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+            let mut spans = self.0.lock().unwrap();
+            let mut fields = Vec::new();
+            attrs.record(&mut FieldVisitor(&mut fields));
+            spans.push((attrs.metadata().name(), fields));
+
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, id: &Id, values: &Record<'_>) {
+            let mut spans = self.0.lock().unwrap();
+            let (_, fields) = &mut spans[id.into_u64() as usize - 1];
+            values.record(&mut FieldVisitor(fields));
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, _: &Event<'_>) {}
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let recorder = SpanRecorder::default();
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+
+    let osu = make_osu(&server);
+    osu.user(2).await.unwrap();
+
+    let spans = recorder.0.lock().unwrap();
+    let (_, fields) = spans
+        .iter()
+        .find(|(name, _)| *name == "request")
+        .expect("missing request span");
+
+    assert!(
+        fields.contains(&"endpoint=get_user".to_owned()),
+        "{:?}",
+        fields
+    );
+    assert!(fields.contains(&"status=200".to_owned()), "{:?}", fields);
+}
+
+#[cfg(feature = "dedup")]
+#[tokio::test]
+async fn dedup_concurrent_requests() {
+    use std::time::Duration;
+
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!([user_json(2, "Badewanne3", 1234.5)]))
+                .set_delay(Duration::from_millis(200)),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+
+    let (a, b, c, d, e) = tokio::join!(
+        osu.user("Badewanne3"),
+        osu.user("Badewanne3"),
+        osu.user("Badewanne3"),
+        osu.user("Badewanne3"),
+        osu.user("Badewanne3"),
+    );
+
+    for user in [a, b, c, d, e] {
+        assert_eq!(user.unwrap().unwrap().user_id, 2);
+    }
+}
+
+#[cfg(feature = "dedup")]
+#[tokio::test]
+async fn dedup_concurrent_errors() {
+    use std::time::Duration;
+
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user"))
+        .respond_with(ResponseTemplate::new(404).set_delay(Duration::from_millis(200)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+
+    let (a, b, c, d, e) = tokio::join!(
+        osu.user("Badewanne3"),
+        osu.user("Badewanne3"),
+        osu.user("Badewanne3"),
+        osu.user("Badewanne3"),
+        osu.user("Badewanne3"),
+    );
+
+    for res in [a, b, c, d, e] {
+        assert!(matches!(res, Err(OsuError::NotFound)));
+    }
+}
+
+#[tokio::test]
+async fn user_fallback_ids() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user"))
+        .and(query_param("u", "old_name"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user"))
+        .and(query_param("u", "2"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!([user_json(2, "new_name", 1234.5)])),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user"))
+        .and(query_param("u", "3"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+    let user = osu
+        .user("old_name")
+        .or_ids(vec![2, 3])
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(user.user_id, 2);
+    assert_eq!(user.username, "new_name");
+}
+
+#[tokio::test]
+async fn scores_mods_including() {
+    let server = MockServer::start().await;
+
+    let hd = GameMods::Hidden;
+    let hdhr = GameMods::Hidden | GameMods::HardRock;
+    let hr = GameMods::HardRock;
+
+    Mock::given(method("GET"))
+        .and(path("/get_scores"))
+        .and(query_param_is_missing("mods"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            score_json(1, 3_000_000, hdhr),
+            score_json(2, 2_000_000, hr),
+            score_json(3, 1_000_000, hd),
+        ])))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+    let scores = osu
+        .scores(905576)
+        .mods(hr)
+        .mods_including(hd)
+        .await
+        .unwrap();
+
+    assert_eq!(scores.len(), 2);
+    assert_eq!(scores[0].enabled_mods, hdhr);
+    assert_eq!(scores[1].enabled_mods, hd);
+
+    // `mods` are ignored regardless of the order in which both are specified
+    let scores = osu
+        .scores(905576)
+        .mods_including(hd)
+        .mods(hr)
+        .await
+        .unwrap();
+
+    assert_eq!(scores.len(), 2);
+}
+
+#[tokio::test]
+async fn default_limits() {
+    let server = MockServer::start().await;
+
+    for (route, limit) in [
+        ("/get_user_best", "100"),
+        ("/get_user_recent", "50"),
+        ("/get_scores", "25"),
+    ] {
+        Mock::given(method("GET"))
+            .and(path(route))
+            .and(query_param("limit", limit))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    Mock::given(method("GET"))
+        .and(path("/get_user_best"))
+        .and(query_param("limit", "5"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let osu = Osu::builder("api_key")
+        .base_url(format!("{}/", server.uri()))
+        .default_top_limit(100)
+        .default_recent_limit(500)
+        .default_leaderboard_limit(25)
+        .build()
+        .unwrap();
+
+    osu.top_scores(2).await.unwrap();
+    osu.recent_scores(2).await.unwrap();
+    osu.scores(905576).await.unwrap();
+    osu.top_scores(2).limit(5).await.unwrap();
+}
+
+#[tokio::test]
+async fn clear_mods() {
+    let server = MockServer::start().await;
+
+    for route in ["/get_scores", "/get_beatmaps"] {
+        Mock::given(method("GET"))
+            .and(path(route))
+            .and(query_param_is_missing("mods"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let osu = make_osu(&server);
+
+    let scores = osu
+        .scores(2271405)
+        .mods(GameMods::Hidden)
+        .clear_mods()
+        .await
+        .unwrap();
+    assert!(scores.is_empty());
+
+    let maps = osu
+        .beatmaps()
+        .mapset_id(1086483)
+        .mods(GameMods::DoubleTime)
+        .clear_mods()
+        .await
+        .unwrap();
+    assert!(maps.is_empty());
+}
+
+#[tokio::test]
+async fn watch_match() {
+    use futures_util::StreamExt;
+    use std::time::Duration;
+
+    let server = MockServer::start().await;
+    let game = || game_json(1, &[(2, 1_000_000)]);
+
+    for body in [
+        match_json(58494587, vec![game()], false),
+        match_json(58494587, vec![game()], false),
+        match_json(58494587, vec![game(), game_json(2, &[(2, 900_000)])], true),
+    ] {
+        Mock::given(method("GET"))
+            .and(path("/get_match"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let osu = make_osu(&server);
+    let updates: Vec<_> = osu
+        .watch_match(58494587, Duration::from_millis(10))
+        .collect()
+        .await;
+
+    assert_eq!(updates.len(), 2);
+    let first = updates[0].as_ref().unwrap();
+    assert_eq!(first.games.len(), 1);
+    assert!(first.end_time.is_none());
+    let second = updates[1].as_ref().unwrap();
+    assert_eq!(second.games.len(), 2);
+    assert!(second.end_time.is_some());
+}
+
+#[tokio::test]
+async fn match_scoreboard() {
+    let server = MockServer::start().await;
+
+    let games = vec![
+        game_json(1, &[(2, 1_000_000), (3, 800_000), (4, 300_000)]),
+        game_json(2, &[(2, 700_000), (3, 950_000)]),
+    ];
+
+    Mock::given(method("GET"))
+        .and(path("/get_match"))
+        .and(query_param("mp", "58494587"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(match_json(58494587, games, true)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+    let scoreboard = osu.match_scoreboard(58494587).await.unwrap();
+
+    let user_ids: Vec<_> = scoreboard.iter().map(|(user_id, _)| *user_id).collect();
+    assert_eq!(user_ids, vec![3, 2, 4]);
+
+    let (_, top) = scoreboard[0];
+    assert_eq!(top.total_score, 1_750_000);
+    assert_eq!(top.games_played, 2);
+    assert_eq!(top.wins, 1);
+}
+
+#[tokio::test]
+async fn beatmap_in_set() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_beatmaps"))
+        .and(query_param("s", "1086483"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            map_json(2271404, 1086483, "Normal", 2.5),
+            map_json(2271405, 1086483, "Mao's Insane", 5.3),
+        ])))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+
+    let map = osu.beatmap_in_set(1086483, "mao's insane").await.unwrap();
+    assert_eq!(map.unwrap().beatmap_id, 2271405);
+
+    let map = osu.beatmap_in_set(1086483, "Hard").await.unwrap();
+    assert!(map.is_none());
+}
+
+#[tokio::test]
+async fn beatmap_with_mod_variants() {
+    let server = MockServer::start().await;
+
+    for (mods, stars) in [(GameMods::DoubleTime, 7.4), (GameMods::HardRock, 5.9)] {
+        Mock::given(method("GET"))
+            .and(path("/get_beatmaps"))
+            .and(query_param("b", "2271405"))
+            .and(query_param("mods", mods.bits().to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([map_json(
+                2271405,
+                1086483,
+                "Mao's Insane",
+                stars
+            )])))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let osu = make_osu(&server);
+    let maps = osu
+        .beatmap_with_mod_variants(2271405, &[GameMods::DoubleTime, GameMods::HardRock])
+        .await
+        .unwrap();
+
+    assert_eq!(maps.len(), 2);
+    assert_eq!(maps[0].0, GameMods::DoubleTime);
+    assert!((maps[0].1.stars - 7.4).abs() < f32::EPSILON);
+    assert_eq!(maps[1].0, GameMods::HardRock);
+    assert!((maps[1].1.stars - 5.9).abs() < f32::EPSILON);
+}
+
+#[tokio::test]
+async fn scores_for_maps() {
+    let server = MockServer::start().await;
+
+    for (map_id, body) in [
+        (
+            "2271405",
+            json!([score_json(2, 1_000_000, GameMods::Hidden)]),
+        ),
+        ("905576", json!([])),
+    ] {
+        Mock::given(method("GET"))
+            .and(path("/get_scores"))
+            .and(query_param("b", map_id))
+            .and(query_param("u", "2"))
+            .and(query_param("m", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let osu = make_osu(&server);
+    let scores = osu
+        .scores_for_maps(&[905576, 2271405], 2, GameMode::Taiko)
+        .await
+        .unwrap();
+
+    assert_eq!(scores.len(), 2);
+    assert_eq!(scores[0].0, 905576);
+    assert!(scores[0].1.is_none());
+    assert_eq!(scores[1].0, 2271405);
+    assert_eq!(
+        scores[1].1.as_ref().map(|score| score.score),
+        Some(1_000_000)
+    );
+}
+
+#[tokio::test]
+async fn replay() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_replay"))
+        .and(query_param("b", "905576"))
+        .and(query_param("u", "2"))
+        .and(query_param("mods", "72"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({ "content": "XQAAIAA=", "encoding": "base64" })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_replay"))
+        .and(query_param("u", "3"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({ "error": "Replay not available." })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+
+    let replay = osu
+        .replay(905576, 2)
+        .mods(GameMods::Hidden | GameMods::DoubleTime)
+        .await
+        .unwrap();
+    assert_eq!(replay.content, vec![0x5d, 0x00, 0x00, 0x20, 0x00]);
+
+    let err = osu.replay(905576, 3).await.unwrap_err();
+    assert!(matches!(err, OsuError::ReplayUnavailable));
+}
+
+#[tokio::test]
+async fn replay_error_message() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_replay"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(
+                json!({ "error": "Requesting too fast! Slow your operation, cap'n!" }),
+            ),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+
+    match osu.replay(905576, 2).await {
+        Err(OsuError::Response { error, status, .. }) => {
+            assert_eq!(
+                error.to_string(),
+                "Requesting too fast! Slow your operation, cap'n!"
+            );
+            assert_eq!(status, 200);
+        }
+        other => panic!("expected response error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn beatmap_leader() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_scores"))
+        .and(query_param("b", "2271405"))
+        .and(query_param("limit", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([score_json(
+            2,
+            1_000_000,
+            GameMods::Hidden
+        )])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+    let map = Beatmap::new(2271405, 1086483);
+    let leader = map.get_leader(&osu).await.unwrap().unwrap();
+    assert_eq!(leader.user_id, 2);
+    assert_eq!(leader.mode, Some(GameMode::Osu));
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user"))
+        .respond_with(
+            ResponseTemplate::new(401)
+                .set_body_json(json!({ "error": "Please provide a valid API key." })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+
+    match osu.user("Badewanne3").await {
+        Err(OsuError::Unauthorized(msg)) => assert_eq!(msg, "Please provide a valid API key."),
+        other => panic!("expected unauthorized error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn beatmaps_stars_between() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_beatmaps"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            map_json(1, 1, "Easy", 1.5),
+            map_json(2, 1, "Hard", 3.5),
+            map_json(3, 1, "Insane", 4.5),
+            map_json(4, 1, "Extra", 6.5),
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+    let maps = osu.beatmaps().stars_between(3.0, 5.0).await.unwrap();
+
+    let map_ids: Vec<_> = maps.iter().map(|map| map.beatmap_id).collect();
+    assert_eq!(map_ids, vec![2, 3]);
+}
+
+#[tokio::test]
+async fn beatmaps_sorted_by_stars() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_beatmaps"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            map_json(1, 1, "Insane", 4.5),
+            map_json(2, 1, "Broken", f32::NAN),
+            map_json(3, 1, "Easy", 1.5),
+            map_json(4, 1, "Hard", 3.5),
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+    let maps = osu.beatmaps().sorted_by_stars().await.unwrap();
+
+    let map_ids: Vec<_> = maps.iter().map(|map| map.beatmap_id).collect();
+    assert_eq!(map_ids, vec![3, 4, 1, 2]);
+}
+
+#[tokio::test]
+async fn beatmap_converted() {
+    let server = MockServer::start().await;
+
+    let mut mania_map = map_json(1, 1, "Insane", 4.5);
+    mania_map["mode"] = json!("3");
+
+    Mock::given(method("GET"))
+        .and(path("/get_beatmaps"))
+        .and(query_param("m", "3"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([mania_map])))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+
+    let map = osu
+        .beatmap()
+        .map_id(1)
+        .as_converted(GameMode::Mania)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(map.mode, GameMode::Mania);
+    assert_eq!(map.converted, None);
+
+    let maps = osu.beatmaps().mode(GameMode::Mania).await.unwrap();
+    assert_eq!(maps[0].converted, Some(false));
+}
+
+#[tokio::test]
+async fn user_bundle() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user"))
+        .and(query_param("u", "Badewanne3"))
+        .and(query_param("m", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([user_json(
+            2,
+            "Badewanne3",
+            1234.5
+        )])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user_best"))
+        .and(query_param("u", "2"))
+        .and(query_param("m", "1"))
+        .and(query_param("limit", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            score_json(2, 3_000_000, GameMods::Hidden),
+            score_json(2, 2_000_000, GameMods::HardRock),
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user_recent"))
+        .and(query_param("u", "2"))
+        .and(query_param("m", "1"))
+        .and(query_param("limit", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([score_json(
+            2,
+            500_000,
+            GameMods::NoMod
+        )])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+    let bundle = osu
+        .user("Badewanne3")
+        .mode(GameMode::Taiko)
+        .bundle()
+        .top_limit(2)
+        .recent_limit(1)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(bundle.user.user_id, 2);
+    assert_eq!(bundle.user.mode, Some(GameMode::Taiko));
+    assert_eq!(bundle.top.len(), 2);
+    assert_eq!(bundle.top[0].score, 3_000_000);
+    assert_eq!(bundle.recent.len(), 1);
+    assert_eq!(bundle.recent[0].mode, Some(GameMode::Taiko));
+}
+
+#[tokio::test]
+async fn scores_best_per_mods() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_scores"))
+        .and(query_param("b", "905576"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            score_json(2, 3_000_000, GameMods::Hidden),
+            score_json(3, 2_500_000, GameMods::HardRock),
+            score_json(4, 2_000_000, GameMods::Hidden),
+            score_json(5, 2_800_000, GameMods::HardRock),
+            score_json(6, 1_000_000, GameMods::NoMod),
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+    let best = osu.scores(905576).best_per_mods().await.unwrap();
+
+    assert_eq!(best.len(), 3);
+    assert_eq!(best[&GameMods::Hidden].user_id, 2);
+    assert_eq!(best[&GameMods::HardRock].user_id, 5);
+    assert_eq!(best[&GameMods::NoMod].score, 1_000_000);
+}
+
+#[tokio::test]
+async fn user_best_with_positions() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user_best"))
+        .and(query_param("u", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            score_json(2, 3_000_000, GameMods::Hidden),
+            score_json(2, 2_000_000, GameMods::HardRock),
+            score_json(2, 1_000_000, GameMods::NoMod),
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+    let scores = osu.top_scores(2).with_positions().await.unwrap();
+
+    let positions: Vec<_> = scores.iter().map(|(pos, _)| *pos).collect();
+    assert_eq!(positions, vec![1, 2, 3]);
+    assert_eq!(scores[0].1.score, 3_000_000);
+    assert_eq!(scores[2].1.score, 1_000_000);
+}
+
+#[tokio::test]
+async fn ratelimit_headers() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user"))
+        .and(query_param("u", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user"))
+        .and(query_param("u", "3"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("X-RateLimit-Remaining", "57")
+                .insert_header("X-RateLimit-Reset", "1600000000")
+                .set_body_json(json!([])),
+        )
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+    osu.user(2).await.unwrap();
+    assert_eq!(osu.last_ratelimit_info(), None);
+
+    osu.user(3).await.unwrap();
+    let info = osu.last_ratelimit_info().unwrap();
+    assert_eq!(info.remaining, Some(57));
+    assert_eq!(info.reset, Some(1_600_000_000));
+
+    // Responses without the headers keep the previous information
+    osu.user(2).await.unwrap();
+    assert_eq!(osu.last_ratelimit_info(), Some(info));
+}
+
+#[tokio::test]
+async fn user_without_events() {
+    let server = MockServer::start().await;
+
+    let mut user = user_json(2, "Badewanne3", 1234.5);
+    user["events"] = json!([{
+        "display_html": "<b><a href='/u/2'>Badewanne3</a></b> achieved rank #1",
+        "beatmap_id": "905576",
+        "beatmapset_id": "415886",
+        "date": "2020-05-01 12:34:56",
+        "epicfactor": "1"
+    }]);
+
+    Mock::given(method("GET"))
+        .and(path("/get_user"))
+        .and(query_param("event_days", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([user])))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+    let with_events = osu.user(2).event_days(1).await.unwrap().unwrap();
+    assert_eq!(with_events.events.len(), 1);
+    assert_eq!(with_events.mode, None);
+
+    let without_events = osu.user(2).without_events().await.unwrap().unwrap();
+    assert!(without_events.events.is_empty());
+}
+
+#[tokio::test]
+async fn scores_require_nonempty() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_scores"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+
+    let scores = osu.scores(u32::MAX).await.unwrap();
+    assert!(scores.is_empty());
+
+    let scores = osu.scores(u32::MAX).require_nonempty().await;
+    assert!(matches!(scores, Err(OsuError::NotFound)));
+}
+
+#[tokio::test]
+async fn scores_tagged_with_mode() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user_best"))
+        .and(query_param("m", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([score_json(
+            2,
+            1_000_000,
+            GameMods::Hidden
+        )])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_scores"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([score_json(
+            2,
+            1_000_000,
+            GameMods::Hidden
+        )])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+
+    let scores = osu.top_scores(2).mode(GameMode::Taiko).await.unwrap();
+    let score = &scores[0];
+    assert_eq!(score.mode, Some(GameMode::Taiko));
+    assert_eq!(score.accuracy_auto(), Some(score.accuracy(GameMode::Taiko)));
+
+    let scores = osu.scores(905576).await.unwrap();
+    assert_eq!(scores[0].mode, None);
+    assert_eq!(scores[0].accuracy_auto(), None);
+}
+
+#[tokio::test]
+async fn beatmaps_collect() {
+    let server = MockServer::start().await;
+
+    let map = |i: u32| {
+        let mut map = map_json(i, i, "Insane", 5.0);
+        map["approved_date"] = json!(format!("2020-01-01 {:02}:{:02}:00", i / 60, i % 60));
+
+        map
+    };
+
+    Mock::given(method("GET"))
+        .and(path("/get_beatmaps"))
+        .and(query_param("limit", "500"))
+        .and(query_param_is_missing("since"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(Value::Array((0..500).map(map).collect())),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // The next page starts with the last map of the previous page
+    Mock::given(method("GET"))
+        .and(path("/get_beatmaps"))
+        .and(query_param("limit", "500"))
+        .and(query_param("since", "2020-01-01 08:19:00"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(Value::Array((499..700).map(map).collect())),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+    let maps = osu.beatmaps_collect(osu.beatmaps(), 600).await.unwrap();
+
+    assert_eq!(maps.len(), 600);
+    let map_ids: Vec<_> = maps.iter().map(|map| map.beatmap_id).collect();
+    assert_eq!(map_ids, (0..600).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn resolve_user() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user"))
+        .and(query_param("u", "badewanne3"))
+        .and(query_param("type", "string"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([user_json(
+            2,
+            "Badewanne3",
+            1234.5
+        )])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_user"))
+        .and(query_param("u", "unknown"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+
+    let resolved = osu.resolve_user("badewanne3").await.unwrap();
+    assert_eq!(resolved, Some((2, "Badewanne3".to_owned())));
+
+    let resolved = osu.resolve_user("unknown").await.unwrap();
+    assert_eq!(resolved, None);
+}
+
+#[tokio::test]
+async fn beatmaps_route_uri() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/get_beatmaps"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!([map_json(1, 100, "Insane", 5.0)])),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let osu = make_osu(&server);
+
+    let req = osu.beatmaps().mapset_id(100).mode(GameMode::Mania).limit(2);
+    let uri = req.route_uri();
+    assert_eq!(uri, "get_beatmaps?&limit=2&s=100&m=3");
+    assert!(!uri.contains("api_key"));
+
+    req.await.unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let sent = &requests[0].url;
+    let sent = format!("{}?{}", sent.path(), sent.query().unwrap());
+    assert_eq!(sent, format!("/{}&k=api_key", uri));
+}
@@ -19,7 +19,7 @@ fn make_osu(api_key: String) -> Osu {
 async fn invalid_api_key() {
     let osu = Osu::new("invalid_api_key");
     let user_error = osu.user(0).await;
-    assert!(matches!(user_error, Err(OsuError::Response { .. })));
+    assert!(matches!(user_error, Err(OsuError::Unauthorized(_))));
 }
 
 #[tokio::test]
@@ -46,7 +46,12 @@ async fn get_user() {
     {
         use prometheus::core::Collector;
         for metric in osu.metrics().collect()[0].get_metric() {
-            let name = metric.get_label()[0].get_value();
+            let name = metric
+                .get_label()
+                .iter()
+                .find(|label| label.get_name() == "type")
+                .unwrap()
+                .get_value();
             let value = metric.get_counter().get_value();
             if ["TopScores", "Users"].contains(&name) {
                 assert_eq!(value as i32, 1);
@@ -69,7 +74,7 @@ async fn get_maps() {
     let osu = init();
     let maps = osu.beatmaps().mapset_id(1086483).await.unwrap();
     assert_eq!(maps.len(), 2);
-    let map = maps.get(0).unwrap();
+    let map = maps.first().unwrap();
     assert_eq!(map.creator, "Mao");
     let leaderboard = map.get_global_leaderboard(&osu).limit(7).await.unwrap();
     assert_eq!(leaderboard.len(), 7);
@@ -85,6 +90,49 @@ async fn get_maps() {
     assert_eq!(maps.len(), 100);
 }
 
+#[tokio::test]
+async fn get_user_maps() {
+    let osu = init();
+    let user = osu.user("Mao").await.unwrap().unwrap();
+    let maps = user.get_beatmaps(&osu).await.unwrap();
+    assert!(!maps.is_empty());
+    assert!(maps.iter().all(|map| map.creator_id == user.user_id));
+}
+
+#[tokio::test]
+async fn get_replay_scores() {
+    let osu = init();
+    let map = osu.beatmap().map_id(905576).await.unwrap().unwrap();
+    let scores = map.get_replay_scores(&osu).limit(50).await.unwrap();
+    assert!(!scores.is_empty());
+    assert!(scores
+        .iter()
+        .all(|score| score.replay_available == Some(true)));
+}
+
+#[tokio::test]
+async fn get_map_leader() {
+    let osu = init();
+    let map = osu.beatmap().map_id(905576).await.unwrap().unwrap();
+    let leader = map.get_leader(&osu).await.unwrap().unwrap();
+    let top = map.get_global_leaderboard(&osu).limit(1).await.unwrap();
+    assert_eq!(top.first(), Some(&leader));
+}
+
+#[tokio::test]
+async fn get_unknown_map() {
+    let osu = init();
+    let map = osu.beatmap().map_id(u32::MAX).await.unwrap();
+    assert!(map.is_none());
+}
+
+#[tokio::test]
+async fn get_scores_unknown_map() {
+    let osu = init();
+    let scores = osu.scores(u32::MAX).await.unwrap();
+    assert!(scores.is_empty());
+}
+
 #[tokio::test]
 async fn get_score() {
     let osu = init();
@@ -148,6 +196,15 @@ async fn get_recent() {
     }
 }
 
+#[tokio::test]
+async fn get_most_recent() {
+    let osu = init();
+    let scores = osu.most_recent_score("mornis").await.unwrap();
+    assert!(scores.len() <= 1);
+    let score = osu.most_recent_score("mornis").latest().await.unwrap();
+    assert_eq!(scores.first(), score.as_ref());
+}
+
 #[tokio::test]
 async fn get_match() {
     let osu = init();
@@ -0,0 +1,83 @@
+extern crate rosu;
+
+use rosu::{model::*, Osu};
+
+fn osu() -> Osu {
+    Osu::new("api_key")
+}
+
+#[test]
+fn route_uri_beatmaps() {
+    let osu = osu();
+
+    let uri = osu.beatmap().map_id(2271405).route_uri();
+    assert_eq!(uri, "get_beatmaps?&limit=1&b=2271405");
+
+    let uri = osu
+        .beatmaps()
+        .mapset_id(1086483)
+        .mode(GameMode::Mania)
+        .limit(2)
+        .route_uri();
+    assert_eq!(uri, "get_beatmaps?&limit=2&s=1086483&m=3");
+}
+
+#[test]
+fn route_uri_match() {
+    let uri = osu().osu_match(58494587).route_uri();
+    assert_eq!(uri, "get_match?mp=58494587");
+}
+
+#[test]
+fn route_uri_replay() {
+    let uri = osu()
+        .replay(905576, 2)
+        .mods(GameMods::Hidden | GameMods::DoubleTime)
+        .route_uri();
+    assert_eq!(uri, "get_replay?b=905576&type=id&u=2&mods=72");
+}
+
+#[test]
+fn route_uri_scores() {
+    let osu = osu();
+
+    let uri = osu.score(905576).user("Badewanne3").route_uri();
+    assert_eq!(uri, "get_scores?b=905576&limit=1&type=string&u=Badewanne3");
+
+    let uri = osu
+        .scores(905576)
+        .mode(GameMode::Taiko)
+        .mods(GameMods::HardRock)
+        .limit(10)
+        .route_uri();
+    assert_eq!(uri, "get_scores?b=905576&limit=10&m=1&mods=16");
+}
+
+#[test]
+fn route_uri_user() {
+    let uri = osu()
+        .user(2)
+        .mode(GameMode::Catch)
+        .event_days(31)
+        .route_uri();
+    assert_eq!(uri, "get_user?type=id&u=2&m=2&event_days=31");
+}
+
+#[test]
+fn route_uri_user_scores() {
+    let osu = osu();
+
+    let uri = osu.top_scores(2).limit(5).route_uri();
+    assert_eq!(uri, "get_user_best?type=id&u=2&limit=5");
+
+    let uri = osu.recent_scores("Badewanne3").route_uri();
+    assert_eq!(uri, "get_user_recent?type=string&u=Badewanne3");
+}
+
+#[test]
+fn route_uri_without_api_key() {
+    let osu = osu();
+
+    assert!(!osu.user(2).route_uri().contains("api_key"));
+    assert!(!osu.beatmaps().route_uri().contains("k="));
+}
@@ -6,8 +6,7 @@ use rosu::model::*;
 #[cfg(feature = "serialize")]
 #[test]
 fn serde_score() {
-    let mut score = Score::default();
-    score.score = 1_000_000;
+    let mut score = Score::new(2, 1_000_000);
     score.enabled_mods = GameMods::from_bits(24).unwrap();
     let serialized = serde_json::to_string(&score).unwrap();
     let deserialized = serde_json::from_str(&serialized).unwrap();
@@ -17,7 +16,7 @@ fn serde_score() {
 #[cfg(feature = "serialize")]
 #[test]
 fn serde_beatmap() {
-    let mut map = Beatmap::default();
+    let mut map = Beatmap::new(2271405, 1086483);
     map.rating = 4.2;
     let serialized = serde_json::to_string(&map).unwrap();
     let deserialized = serde_json::from_str(&serialized).unwrap();
@@ -27,7 +26,7 @@ fn serde_beatmap() {
 #[cfg(feature = "serialize")]
 #[test]
 fn serde_user() {
-    let mut user = User::default();
+    let mut user = User::new(2, "Badewanne3");
     user.accuracy = 97.65;
     let serialized = serde_json::to_string(&user).unwrap();
     let deserialized = serde_json::from_str(&serialized).unwrap();